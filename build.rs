@@ -0,0 +1,98 @@
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+const DATA_DIR: &str = "data";
+
+fn is_year(name: &str) -> bool {
+    name.strip_prefix('y')
+        .is_some_and(|year| year.chars().all(|c| c.is_ascii_digit()))
+}
+
+// a file name as part of a test name, so day15-example.txt and the like
+// always make a valid identifier
+fn stem(file: &str) -> String {
+    let stem = file
+        .trim_end_matches(".txt")
+        .replace(|c: char| !c.is_ascii_alphanumeric() && c != '_', "_");
+
+    format!("f{stem}")
+}
+
+fn tests(manifest: &Path, profile: &str) -> String {
+    let text = fs::read_to_string(manifest).expect("answers manifest");
+    let table = text.parse::<toml::Table>().expect("valid answers manifest");
+
+    let mut tests = String::new();
+
+    for (year, days) in &table {
+        for (day, files) in days.as_table().expect("days table") {
+            let day = day.parse::<u8>().expect("day as table key");
+
+            for (file, parts) in files.as_table().expect("files table") {
+                let stem = stem(file);
+
+                for (part, value) in parts.as_table().expect("parts table") {
+                    // not a part, see manifest::PARAMS
+                    if part == "params" {
+                        continue;
+                    }
+
+                    let number = part.trim_start_matches("part");
+                    let ignore = value.get("ignore").and_then(|v| v.as_str());
+
+                    writeln!(tests, "#[test]").unwrap();
+                    if let Some(reason) = ignore {
+                        writeln!(tests, "#[ignore = {reason:?}]").unwrap();
+                    }
+                    writeln!(
+                        tests,
+                        "fn y{year}_{stem}_{part}() {{\n    \
+                         crate::manifest::verify({profile:?}, {year}, {day}, \
+                         {file:?}, {number});\n\
+                         }}\n"
+                    )
+                    .unwrap();
+                }
+            }
+        }
+    }
+
+    tests
+}
+
+// generates a test per answer in the manifest of each profile, see
+// manifest::verify
+fn main() {
+    println!("cargo:rerun-if-changed={DATA_DIR}");
+
+    let mut generated = tests(&Path::new(DATA_DIR).join("answers.toml"), "");
+
+    let mut profiles = fs::read_dir(DATA_DIR)
+        .expect("data directory")
+        .map(|entry| entry.expect("data directory entry").path())
+        .filter(|path| path.join("answers.toml").is_file())
+        .collect::<Vec<_>>();
+    profiles.sort();
+
+    for path in profiles {
+        let name = path.file_name().unwrap().to_string_lossy().to_string();
+
+        if is_year(&name) {
+            continue;
+        }
+
+        let module = name.replace(|c: char| !c.is_ascii_alphanumeric(), "_");
+
+        writeln!(
+            generated,
+            "mod profile_{module} {{\n{}}}\n",
+            tests(&path.join("answers.toml"), &name)
+        )
+        .unwrap();
+    }
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("answers.rs");
+    fs::write(out, generated).expect("generated answer tests");
+}
@@ -0,0 +1,366 @@
+use crate::error::ParseError;
+use crate::manifest::syntax_error;
+use crate::registry::Puzzle;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::hint;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use toml::{Table, Value};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub median: Duration,
+    pub min: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty(), "at least one sample is needed");
+
+        samples.sort_unstable();
+
+        let mid = samples.len() / 2;
+        #[allow(clippy::manual_is_multiple_of)]
+        let median = if samples.len() % 2 == 0 {
+            (samples[mid - 1] + samples[mid]) / 2
+        } else {
+            samples[mid]
+        };
+
+        Self { median, min: samples[0] }
+    }
+}
+
+fn time<T>(iterations: usize, mut f: impl FnMut() -> T) -> (Stats, T) {
+    let mut samples = Vec::with_capacity(iterations);
+    let mut result = None;
+
+    for _ in 0..iterations.max(1) {
+        let start = Instant::now();
+        let value = hint::black_box(f());
+        samples.push(start.elapsed());
+
+        // replacing the last result drops it outside the timed section
+        result = Some(value);
+    }
+
+    (Stats::from_samples(samples), result.expect("at least one iteration"))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Measurement {
+    pub year: u16,
+    pub day: u8,
+    pub phases: Vec<(&'static str, Stats)>,
+}
+
+pub fn measure(
+    puzzle: &Puzzle,
+    input: &str,
+    iterations: usize,
+) -> Result<Measurement, ParseError> {
+    let (stats, parsed) = time(iterations, || puzzle.parse(input));
+    let parsed = parsed?;
+
+    let mut phases = vec![("parse", stats)];
+
+    for (phase, part) in [("part1", 1), ("part2", 2)] {
+        let (stats, answer) = time(iterations, || parsed.part(part));
+
+        if answer.is_some_and(|answer| answer.is_solved()) {
+            phases.push((phase, stats));
+        }
+    }
+
+    Ok(Measurement { year: puzzle.year, day: puzzle.day, phases })
+}
+
+pub fn table(measurements: &[Measurement]) -> String {
+    let mut out = format!(
+        "{:<12} {:<6} {:>12} {:>12}\n",
+        "puzzle", "phase", "median", "min"
+    );
+
+    for m in measurements {
+        for (phase, stats) in &m.phases {
+            writeln!(
+                out,
+                "{:<12} {:<6} {:>12} {:>12}",
+                format!("{} day {:02}", m.year, m.day),
+                phase,
+                format!("{:.1?}", stats.median),
+                format!("{:.1?}", stats.min)
+            )
+            .unwrap();
+        }
+    }
+
+    out
+}
+
+pub fn json(measurements: &[Measurement]) -> String {
+    let days = measurements
+        .iter()
+        .map(|m| {
+            let phases = m
+                .phases
+                .iter()
+                .map(|(phase, stats)| {
+                    format!(
+                        "\"{phase}\": {{\"median_ns\": {}, \"min_ns\": {}}}",
+                        stats.median.as_nanos(),
+                        stats.min.as_nanos()
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            format!("  {{\"year\": {}, \"day\": {}, {phases}}}", m.year, m.day)
+        })
+        .collect::<Vec<_>>()
+        .join(",\n");
+
+    format!("[\n{days}\n]\n")
+}
+
+// baselines store the median of each phase in nanoseconds, keyed like the
+// answers manifest
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Baseline {
+    medians: HashMap<(u16, u8, String), Duration>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Regression {
+    pub year: u16,
+    pub day: u8,
+    pub phase: String,
+    pub baseline: Duration,
+    pub current: Duration,
+}
+
+impl Baseline {
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let table =
+            text.parse::<Table>().map_err(|err| syntax_error(text, &err))?;
+
+        let mut medians = HashMap::new();
+
+        for (year, days) in &table {
+            let days = days
+                .as_table()
+                .ok_or_else(|| error_at(text, &[year], "a table"))?;
+
+            for (day, phases) in days {
+                let phases = phases
+                    .as_table()
+                    .ok_or_else(|| error_at(text, &[year, day], "a table"))?;
+
+                for (phase, value) in phases {
+                    let nanos = value
+                        .as_integer()
+                        .and_then(|n| u64::try_from(n).ok())
+                        .ok_or_else(|| {
+                            error_at(
+                                text,
+                                &[year, day, phase],
+                                "a duration in nanoseconds",
+                            )
+                        })?;
+
+                    medians.insert(
+                        (
+                            number(text, &[year])?,
+                            number(text, &[year, day])?,
+                            phase.clone(),
+                        ),
+                        Duration::from_nanos(nanos),
+                    );
+                }
+            }
+        }
+
+        Ok(Self { medians })
+    }
+
+    pub fn from_measurements(measurements: &[Measurement]) -> Self {
+        let medians = measurements
+            .iter()
+            .flat_map(|m| {
+                m.phases.iter().map(|(phase, stats)| {
+                    ((m.year, m.day, phase.to_string()), stats.median)
+                })
+            })
+            .collect();
+
+        Self { medians }
+    }
+
+    // puzzles missing from `self` keep their previous baseline
+    pub fn merge(&mut self, other: Self) {
+        self.medians.extend(other.medians);
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut table = Table::new();
+
+        for ((year, day, phase), median) in &self.medians {
+            let days = table
+                .entry(year.to_string())
+                .or_insert_with(|| Value::Table(Table::new()));
+            let phases = days
+                .as_table_mut()
+                .unwrap()
+                .entry(format!("{day:02}"))
+                .or_insert_with(|| Value::Table(Table::new()));
+            let nanos = i64::try_from(median.as_nanos()).unwrap_or(i64::MAX);
+
+            phases
+                .as_table_mut()
+                .unwrap()
+                .insert(phase.clone(), Value::Integer(nanos));
+        }
+
+        table.to_string()
+    }
+
+    pub fn compare(
+        &self,
+        measurements: &[Measurement],
+        threshold: f64,
+    ) -> Vec<Regression> {
+        let mut regressions = Vec::new();
+
+        for m in measurements {
+            for (phase, stats) in &m.phases {
+                let key = (m.year, m.day, phase.to_string());
+                let Some(&baseline) = self.medians.get(&key) else {
+                    continue;
+                };
+
+                if stats.median.as_secs_f64()
+                    > baseline.as_secs_f64() * (1.0 + threshold / 100.0)
+                {
+                    regressions.push(Regression {
+                        year: m.year,
+                        day: m.day,
+                        phase: phase.to_string(),
+                        baseline,
+                        current: stats.median,
+                    });
+                }
+            }
+        }
+
+        regressions
+    }
+}
+
+// the last of `keys` as a number
+fn number<N: FromStr>(text: &str, keys: &[&str]) -> Result<N, ParseError> {
+    keys[keys.len() - 1].parse().map_err(|_| error_at(text, keys, "a number"))
+}
+
+// toml tables do not keep the spans of their keys, so errors point at the
+// last of `keys` found one after another, a phase under its own day rather
+// than the first day that mentions it
+fn error_at(text: &str, keys: &[&str], expected: &str) -> ParseError {
+    let err = ParseError::new(expected);
+    let lines = text.lines().collect::<Vec<_>>();
+    let (mut row, mut from, mut start) = (0, 0, 0);
+
+    for key in keys {
+        let found = (row..lines.len()).find_map(|r| {
+            let skip = if r == row { from } else { 0 };
+            lines[r][skip..].find(key).map(|col| (r, skip + col))
+        });
+        let Some((r, col)) = found else {
+            return err;
+        };
+
+        (row, from, start) = (r, col + key.len(), col);
+    }
+
+    match lines.get(row) {
+        Some(line) => err.at(row + 1, line[..start].chars().count() + 1),
+        None => err,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&ms| Duration::from_millis(ms)).collect()
+    }
+
+    fn measurement(parse: u64, part1: u64) -> Measurement {
+        let stats = |ms| Stats::from_samples(millis(&[ms]));
+
+        Measurement {
+            year: 2022,
+            day: 16,
+            phases: vec![("parse", stats(parse)), ("part1", stats(part1))],
+        }
+    }
+
+    #[test]
+    fn median_and_min() {
+        let stats = Stats::from_samples(millis(&[5, 1, 3]));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.min, Duration::from_millis(1));
+
+        let stats = Stats::from_samples(millis(&[4, 1, 3, 8]));
+        assert_eq!(stats.median, Duration::from_micros(3500));
+    }
+
+    #[test]
+    fn json_output() {
+        assert_eq!(
+            json(&[measurement(1, 2)]),
+            "[\n  {\"year\": 2022, \"day\": 16, \
+             \"parse\": {\"median_ns\": 1000000, \"min_ns\": 1000000}, \
+             \"part1\": {\"median_ns\": 2000000, \"min_ns\": 2000000}}\n]\n"
+        );
+    }
+
+    #[test]
+    fn baseline_round_trip() {
+        let baseline = Baseline::from_measurements(&[measurement(1, 2)]);
+        let text = baseline.to_toml();
+
+        assert!(text.contains("parse = 1000000"));
+        assert_eq!(Baseline::parse(&text), Ok(baseline));
+    }
+
+    #[test]
+    fn baseline_errors_have_lines() {
+        let text = "[2022.16]\nparse = 5\npart1 = 7\n\n\
+                    [2022.17]\nparse = 3\npart1 = \"slow\"\n";
+        let err = Baseline::parse(text).unwrap_err();
+        assert_eq!((err.line, err.column), (7, 1));
+        assert_eq!(err.expected, "a duration in nanoseconds");
+
+        let err = Baseline::parse("[2022.16]\nparse = \n").unwrap_err();
+        assert_eq!(err.line, 2);
+
+        let err = Baseline::parse("[2022.x]\nparse = 5\n").unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (1, 7, "a number")
+        );
+    }
+
+    #[test]
+    fn slower_phases_are_flagged() {
+        let baseline = Baseline::from_measurements(&[measurement(10, 100)]);
+        let regressions = baseline.compare(&[measurement(11, 150)], 20.0);
+
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].phase, "part1");
+        assert_eq!(regressions[0].baseline, Duration::from_millis(100));
+        assert_eq!(regressions[0].current, Duration::from_millis(150));
+    }
+}
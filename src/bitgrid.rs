@@ -0,0 +1,403 @@
+use crate::grid::{Direction, Position2D};
+use crate::point::Point;
+use std::ops::{BitAnd, BitOr, Sub};
+
+const BITS: i32 = u64::BITS as i32;
+
+// a set of cells packed a bit each, every row a run of words with bit 0 the
+// leftmost cell of its word, growing whenever a cell lands outside. grids
+// sharing a layout combine a word at a time
+#[derive(Debug, Clone, Default)]
+pub struct BitGrid {
+    origin: Position2D,
+    words: usize,
+    height: usize,
+    bits: Vec<u64>,
+}
+
+impl BitGrid {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // room for every cell between both corners
+    fn blank(min: Position2D, max: Position2D) -> Self {
+        let words = (max.x - min.x).div_euclid(BITS) as usize + 1;
+        let height = (max.y - min.y) as usize + 1;
+
+        Self { origin: min, words, height, bits: vec![0; words * height] }
+    }
+
+    // the bottom right cell of the layout
+    fn corner(&self) -> Position2D {
+        let (width, height) = (self.words as i32 * BITS, self.height as i32);
+
+        self.origin + Position2D::new(width - 1, height - 1)
+    }
+
+    fn same_layout(&self, other: &Self) -> bool {
+        (self.origin, self.words, self.height)
+            == (other.origin, other.words, other.height)
+    }
+
+    fn index(&self, p: Position2D) -> Option<(usize, u64)> {
+        if self.bits.is_empty() || !p.within(&self.origin, &self.corner()) {
+            return None;
+        }
+
+        let (x, y) =
+            ((p.x - self.origin.x) as usize, (p.y - self.origin.y) as usize);
+        let bits = BITS as usize;
+
+        Some((y * self.words + x / bits, 1 << (x % bits)))
+    }
+
+    // the same cells in a layout reaching both corners too, moving whole
+    // words so the bits keep their place within them
+    fn cover(&self, min: Position2D, max: Position2D) -> Self {
+        if self.bits.is_empty() {
+            return Self::blank(min, max);
+        }
+
+        let (min, max) = (min.lower(self.origin), max.upper(self.corner()));
+        let left = (self.origin.x - min.x + BITS - 1) / BITS;
+        let origin = Position2D::new(self.origin.x - left * BITS, min.y);
+
+        let mut grid = Self::blank(origin, max);
+        let top = (self.origin.y - origin.y) as usize;
+
+        for (y, row) in self.bits.chunks(self.words).enumerate() {
+            let start = (top + y) * grid.words + left as usize;
+            grid.bits[start..start + self.words].copy_from_slice(row);
+        }
+
+        grid
+    }
+
+    // grows to hold both corners, by at least the current size on each side
+    // that grows so that cells landing one after another outside stay cheap
+    fn reserve(&mut self, min: Position2D, max: Position2D) {
+        if self.bits.is_empty() {
+            *self = Self::blank(min, max);
+            return;
+        }
+
+        let (lower, upper) = (self.origin, self.corner());
+        if min.within(&lower, &upper) && max.within(&lower, &upper) {
+            return;
+        }
+
+        let span = upper - lower + Position2D::new(1, 1);
+        let (mut min, mut max) = (min.lower(lower), max.upper(upper));
+        if min.x < lower.x {
+            min.x = min.x.min(lower.x - span.x);
+        }
+        if min.y < lower.y {
+            min.y = min.y.min(lower.y - span.y);
+        }
+        if max.x > upper.x {
+            max.x = max.x.max(upper.x + span.x);
+        }
+        if max.y > upper.y {
+            max.y = max.y.max(upper.y + span.y);
+        }
+
+        *self = self.cover(min, max);
+    }
+
+    pub fn contains(&self, p: Position2D) -> bool {
+        self.index(p).is_some_and(|(i, bit)| self.bits[i] & bit != 0)
+    }
+
+    // true when the cell was not set yet
+    pub fn insert(&mut self, p: Position2D) -> bool {
+        self.reserve(p, p);
+
+        let (i, bit) = self.index(p).expect("reserved cell");
+        let added = self.bits[i] & bit == 0;
+        self.bits[i] |= bit;
+        added
+    }
+
+    // true when the cell was set
+    pub fn remove(&mut self, p: Position2D) -> bool {
+        let Some((i, bit)) = self.index(p) else {
+            return false;
+        };
+
+        let removed = self.bits[i] & bit != 0;
+        self.bits[i] &= !bit;
+        removed
+    }
+
+    pub fn len(&self) -> usize {
+        self.bits.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits.iter().all(|&word| word == 0)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Position2D> + '_ {
+        self.bits.iter().enumerate().flat_map(move |(i, &(mut word))| {
+            let (y, column) = (i / self.words, i % self.words);
+            let start =
+                self.origin + Position2D::new(column as i32 * BITS, y as i32);
+
+            // the lowest set bit, clearing it each time
+            std::iter::from_fn(move || {
+                let x = word.trailing_zeros() as i32;
+                word &= word.wrapping_sub(1);
+
+                (x < BITS).then(|| start + Position2D::new(x, 0))
+            })
+        })
+    }
+
+    // the 64 cells of row y from x rightwards, x as bit 0
+    pub fn word(&self, x: i32, y: i32) -> u64 {
+        let word = |column: i32| {
+            let p = self.origin
+                + Position2D::new(column * BITS, y - self.origin.y);
+            self.index(p).map_or(0, |(i, _)| self.bits[i])
+        };
+
+        let x = x - self.origin.x;
+        let (column, shift) = (x.div_euclid(BITS), x.rem_euclid(BITS));
+
+        if shift == 0 {
+            word(column)
+        } else {
+            (word(column) >> shift) | (word(column + 1) << (BITS - shift))
+        }
+    }
+
+    // sets the cells of row y from x rightwards that are set in `bits`, x as
+    // bit 0
+    pub fn insert_word(&mut self, x: i32, y: i32, bits: u64) {
+        if bits == 0 {
+            return;
+        }
+
+        let last = BITS - 1 - bits.leading_zeros() as i32;
+        self.reserve(Position2D::new(x, y), Position2D::new(x + last, y));
+
+        let x = (x - self.origin.x) as usize;
+        let row = (y - self.origin.y) as usize * self.words;
+        let (column, shift) = (x / BITS as usize, x % BITS as usize);
+
+        self.bits[row + column] |= bits << shift;
+        if shift > 0 && bits >> (BITS as usize - shift) != 0 {
+            self.bits[row + column + 1] |= bits >> (BITS as usize - shift);
+        }
+    }
+
+    // every cell moved one step towards `direction`
+    pub fn shifted(&self, direction: Direction) -> Self {
+        let step = direction.to_offset();
+        let rows = self.bits.chunks(self.words.max(1));
+
+        // make room first if any cell would fall off the edge
+        let spills = match step.y {
+            -1 => rows
+                .clone()
+                .next()
+                .is_some_and(|row| row.iter().any(|&w| w != 0)),
+            1 => rows
+                .clone()
+                .last()
+                .is_some_and(|row| row.iter().any(|&w| w != 0)),
+            _ => false,
+        } || match step.x {
+            -1 => rows.clone().any(|row| row[0] & 1 != 0),
+            1 => rows.clone().any(|row| row[row.len() - 1] >> (BITS - 1) != 0),
+            _ => false,
+        };
+
+        let mut grid = self.clone();
+        if spills {
+            let edge = |p: Position2D| p + step;
+            grid.reserve(edge(self.origin), edge(self.corner()));
+        }
+
+        let words = grid.words;
+        match step.y {
+            -1 => {
+                grid.bits.rotate_left(words);
+                grid.bits.iter_mut().rev().take(words).for_each(|w| *w = 0);
+            }
+            1 => {
+                grid.bits.rotate_right(words);
+                grid.bits.iter_mut().take(words).for_each(|w| *w = 0);
+            }
+            _ => {}
+        }
+
+        for row in grid.bits.chunks_mut(words.max(1)) {
+            match step.x {
+                -1 => {
+                    for i in 0..row.len() {
+                        let carry =
+                            row.get(i + 1).map_or(0, |w| w << (BITS - 1));
+                        row[i] = (row[i] >> 1) | carry;
+                    }
+                }
+                1 => {
+                    for i in (0..row.len()).rev() {
+                        let carry =
+                            if i > 0 { row[i - 1] >> (BITS - 1) } else { 0 };
+                        row[i] = (row[i] << 1) | carry;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        grid
+    }
+
+    // combines both grids a word at a time, bringing them to a common layout
+    // first when they differ
+    fn zip(&self, other: &Self, op: impl Fn(u64, u64) -> u64) -> Self {
+        if !self.same_layout(other) {
+            let lhs = if other.bits.is_empty() {
+                self.clone()
+            } else {
+                self.cover(other.origin, other.corner())
+            };
+            let mut rhs =
+                Self { bits: vec![0; lhs.bits.len()], ..lhs.clone() };
+            other.iter().for_each(|p| {
+                rhs.insert(p);
+            });
+
+            return lhs.zip(&rhs, op);
+        }
+
+        Self {
+            origin: self.origin,
+            words: self.words,
+            height: self.height,
+            bits: self
+                .bits
+                .iter()
+                .zip(&other.bits)
+                .map(|(&a, &b)| op(a, b))
+                .collect(),
+        }
+    }
+}
+
+impl FromIterator<Position2D> for BitGrid {
+    fn from_iter<I: IntoIterator<Item = Position2D>>(iter: I) -> Self {
+        let mut grid = Self::new();
+        grid.extend(iter);
+        grid
+    }
+}
+
+impl Extend<Position2D> for BitGrid {
+    fn extend<I: IntoIterator<Item = Position2D>>(&mut self, iter: I) {
+        for p in iter {
+            self.insert(p);
+        }
+    }
+}
+
+impl BitAnd for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitand(self, other: Self) -> BitGrid {
+        self.zip(other, |a, b| a & b)
+    }
+}
+
+impl BitOr for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitor(self, other: Self) -> BitGrid {
+        self.zip(other, |a, b| a | b)
+    }
+}
+
+// the cells set in the left grid but not in the right one
+impl Sub for &BitGrid {
+    type Output = BitGrid;
+
+    fn sub(self, other: Self) -> BitGrid {
+        self.zip(other, |a, b| a & !b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cells(grid: &BitGrid) -> Vec<Position2D> {
+        let mut cells = grid.iter().collect::<Vec<_>>();
+        cells.sort_unstable_by_key(|p| (p.y, p.x));
+        cells
+    }
+
+    #[test]
+    fn growing_set() {
+        let points = [(0, 0), (-70, 3), (130, -2), (5, 200), (0, 0)]
+            .map(|(x, y)| Position2D::new(x, y));
+
+        let mut grid = BitGrid::new();
+        let added = points.map(|p| grid.insert(p));
+
+        assert_eq!(added, [true, true, true, true, false]);
+        assert_eq!(grid.len(), 4);
+        assert!(points.iter().all(|&p| grid.contains(p)));
+        assert!(!grid.contains(Position2D::new(1, 0)));
+
+        assert!(grid.remove(Position2D::new(0, 0)));
+        assert!(!grid.remove(Position2D::new(0, 0)));
+        assert_eq!(cells(&grid), [points[2], points[1], points[3]].to_vec());
+    }
+
+    #[test]
+    fn shifts_and_combinations() {
+        let grid = [(0, 0), (63, 0), (1, 1), (2, 1)]
+            .map(|(x, y)| Position2D::new(x, y))
+            .into_iter()
+            .collect::<BitGrid>();
+
+        for direction in Direction::ALL {
+            let moved = grid.iter().map(|p| p + direction).collect::<Vec<_>>();
+            let shifted = grid.shifted(direction);
+
+            assert_eq!(shifted.len(), 4);
+            assert!(
+                moved.iter().all(|&p| shifted.contains(p)),
+                "{direction:?}"
+            );
+        }
+
+        let right = grid.shifted(Direction::Right);
+        assert_eq!(cells(&(&grid & &right)), [Position2D::new(2, 1)]);
+        assert_eq!((&grid | &right).len(), 7);
+        assert_eq!(
+            cells(&(&grid - &right)),
+            [(0, 0), (63, 0), (1, 1)].map(|(x, y)| Position2D::new(x, y))
+        );
+
+        let elsewhere =
+            [Position2D::new(-100, 1)].into_iter().collect::<BitGrid>();
+        assert_eq!((&grid | &elsewhere).len(), 5);
+    }
+
+    #[test]
+    fn words() {
+        let mut grid = BitGrid::new();
+        grid.insert_word(60, 2, 0b1011);
+
+        assert_eq!(grid.word(60, 2), 0b1011);
+        assert_eq!(grid.word(61, 2), 0b101);
+        assert_eq!(grid.word(0, 2), 0b1011 << 60);
+        assert_eq!(grid.word(60, 3), 0);
+        assert!(grid.contains(Position2D::new(63, 2)));
+        assert_eq!(grid.len(), 3);
+    }
+}
@@ -0,0 +1,567 @@
+use crate::solution::Answer;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+const USER_AGENT: &str = "github.com/jacoelho/advent-of-code-r";
+
+// the site asks for at least a minute between wrong answers
+const RETRY_AFTER_WRONG: Duration = Duration::from_secs(60);
+
+#[derive(Debug)]
+pub enum ClientError {
+    MissingSession,
+    Io(io::Error),
+    Http(String),
+    RateLimited(Duration),
+    SolvedWith(String),
+    UnexpectedResponse(String),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::MissingSession => write!(f, "missing session token"),
+            Self::Io(err) => write!(f, "{err}"),
+            Self::Http(err) => write!(f, "request failed: {err}"),
+            Self::RateLimited(wait) => {
+                write!(f, "rate limited, retry in {}s", wait.as_secs())
+            }
+            Self::SolvedWith(answer) => {
+                write!(f, "already solved with {}", escape(answer))
+            }
+            Self::UnexpectedResponse(body) => {
+                write!(f, "unexpected response: {body}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<io::Error> for ClientError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<ureq::Error> for ClientError {
+    fn from(err: ureq::Error) -> Self {
+        Self::Http(err.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    AlreadySolved,
+    Wait(Duration),
+}
+
+impl Verdict {
+    fn parse(body: &str) -> Option<Self> {
+        let verdict = if body.contains("That's the right answer") {
+            Self::Correct
+        } else if body.contains("your answer is too high") {
+            Self::TooHigh
+        } else if body.contains("your answer is too low") {
+            Self::TooLow
+        } else if body.contains("That's not the right answer") {
+            Self::Incorrect
+        } else if body.contains("Did you already complete it") {
+            Self::AlreadySolved
+        } else if body.contains("You gave an answer too recently") {
+            Self::Wait(parse_wait(body).unwrap_or(RETRY_AFTER_WRONG))
+        } else {
+            return None;
+        };
+
+        Some(verdict)
+    }
+
+    const fn is_wrong(self) -> bool {
+        matches!(self, Self::Incorrect | Self::TooHigh | Self::TooLow)
+    }
+
+    // how long the site blocks submissions after this verdict
+    const fn retry_after(self) -> Duration {
+        match self {
+            Self::Wait(wait) => wait,
+            _ if self.is_wrong() => RETRY_AFTER_WRONG,
+            _ => Duration::ZERO,
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Correct => write!(f, "correct"),
+            Self::Incorrect => write!(f, "incorrect"),
+            Self::TooHigh => write!(f, "too_high"),
+            Self::TooLow => write!(f, "too_low"),
+            Self::AlreadySolved => write!(f, "already_solved"),
+            Self::Wait(wait) => write!(f, "wait_{}", wait.as_secs()),
+        }
+    }
+}
+
+impl std::str::FromStr for Verdict {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let verdict = match s {
+            "correct" => Self::Correct,
+            "incorrect" => Self::Incorrect,
+            "too_high" => Self::TooHigh,
+            "too_low" => Self::TooLow,
+            "already_solved" => Self::AlreadySolved,
+            _ => {
+                let secs = s.strip_prefix("wait_").ok_or(())?;
+                Self::Wait(Duration::from_secs(secs.parse().map_err(|_| ())?))
+            }
+        };
+
+        Ok(verdict)
+    }
+}
+
+// "You have 1m 32s left to wait."
+fn parse_wait(body: &str) -> Option<Duration> {
+    let (before, _) = body.split_once(" left to wait")?;
+    let (_, wait) = before.rsplit_once("You have ")?;
+
+    wait.split_whitespace().try_fold(Duration::ZERO, |total, unit| {
+        let (last, _) = unit.char_indices().next_back()?;
+        let secs = match unit.split_at(last) {
+            (n, "m") => n.parse::<u64>().ok()? * 60,
+            (n, "s") => n.parse::<u64>().ok()?,
+            _ => return None,
+        };
+
+        Some(total + Duration::from_secs(secs))
+    })
+}
+
+// answers are kept one per line between tabs, so tabs and line breaks in
+// them, as in rendered answers, are written as escapes
+fn escape(answer: &str) -> String {
+    answer
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+fn unescape(field: &str) -> Option<String> {
+    let mut answer = String::with_capacity(field.len());
+    let mut chars = field.chars();
+
+    while let Some(c) = chars.next() {
+        answer.push(match c {
+            '\\' => match chars.next()? {
+                '\\' => '\\',
+                't' => '\t',
+                'n' => '\n',
+                'r' => '\r',
+                _ => return None,
+            },
+            c => c,
+        });
+    }
+
+    Some(answer)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    pub year: u16,
+    pub day: u8,
+    pub part: usize,
+    pub at: u64,
+    pub verdict: Verdict,
+    pub answer: String,
+}
+
+impl Attempt {
+    fn parse(line: &str) -> Option<Self> {
+        let mut fields = line.splitn(6, '\t');
+
+        Some(Self {
+            year: fields.next()?.parse().ok()?,
+            day: fields.next()?.parse().ok()?,
+            part: fields.next()?.parse().ok()?,
+            at: fields.next()?.parse().ok()?,
+            verdict: fields.next()?.parse().ok()?,
+            answer: unescape(fields.next()?)?,
+        })
+    }
+}
+
+impl fmt::Display for Attempt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}\t{}\t{}",
+            self.year,
+            self.day,
+            self.part,
+            self.at,
+            self.verdict,
+            escape(&self.answer)
+        )
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+pub struct Client {
+    base_url: String,
+    session: Option<String>,
+    data_dir: PathBuf,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(session: Option<String>) -> Self {
+        Self {
+            base_url: BASE_URL.to_string(),
+            session,
+            data_dir: PathBuf::from("data"),
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    // AOC_SESSION holds the session cookie, AOC_BASE_URL points the client
+    // at a stand-in server
+    pub fn from_env() -> Self {
+        let client = Self::new(std::env::var("AOC_SESSION").ok());
+
+        match std::env::var("AOC_BASE_URL") {
+            Ok(url) => client.with_base_url(&url),
+            Err(_) => client,
+        }
+    }
+
+    pub fn with_base_url(self, url: &str) -> Self {
+        Self { base_url: url.trim_end_matches('/').to_string(), ..self }
+    }
+
+    pub fn with_data_dir(self, dir: impl Into<PathBuf>) -> Self {
+        Self { data_dir: dir.into(), ..self }
+    }
+
+    pub fn input_path(&self, year: u16, day: u8) -> PathBuf {
+        self.data_dir.join(format!("y{year}/day{day:02}.txt"))
+    }
+
+    pub fn history_path(&self) -> PathBuf {
+        self.data_dir.join("submissions.tsv")
+    }
+
+    fn cookie(&self) -> Result<String, ClientError> {
+        let session =
+            self.session.as_ref().ok_or(ClientError::MissingSession)?;
+
+        Ok(format!("session={session}"))
+    }
+
+    // returns the cached input, downloading it first when missing or when
+    // only a placeholder exists
+    pub fn input(&self, year: u16, day: u8) -> Result<String, ClientError> {
+        let path = self.input_path(year, day);
+
+        match fs::read_to_string(&path) {
+            Ok(input) if !input.is_empty() => return Ok(input),
+            Ok(_) => {}
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err.into()),
+        }
+
+        let input = self
+            .agent
+            .get(&format!("{}/{year}/day/{day}/input", self.base_url))
+            .set("Cookie", &self.cookie()?)
+            .call()?
+            .into_string()?;
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, &input)?;
+
+        Ok(input)
+    }
+
+    pub fn history(&self) -> Result<Vec<Attempt>, ClientError> {
+        match fs::read_to_string(self.history_path()) {
+            Ok(text) => Ok(text.lines().filter_map(Attempt::parse).collect()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn record(&self, attempt: &Attempt) -> Result<(), ClientError> {
+        use std::io::Write;
+
+        fs::create_dir_all(&self.data_dir)?;
+
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.history_path())?;
+
+        writeln!(file, "{attempt}")?;
+
+        Ok(())
+    }
+
+    // answers already judged are answered from the history, and nothing is
+    // sent while the site still blocks submissions for that day. a part
+    // already solved with another answer is an error, as that one is right
+    pub fn submit(
+        &self,
+        year: u16,
+        day: u8,
+        part: usize,
+        answer: &Answer,
+    ) -> Result<Verdict, ClientError> {
+        let answer = answer.to_string();
+        let history = self.history()?;
+        let attempts = history
+            .iter()
+            .filter(|a| a.year == year && a.day == day)
+            .collect::<Vec<_>>();
+
+        let known = attempts.iter().filter(|a| a.part == part);
+
+        for attempt in known {
+            match attempt.verdict {
+                Verdict::Correct if attempt.answer == answer => {
+                    return Ok(Verdict::Correct)
+                }
+                Verdict::Correct => {
+                    return Err(ClientError::SolvedWith(
+                        attempt.answer.clone(),
+                    ))
+                }
+                verdict if verdict.is_wrong() && attempt.answer == answer => {
+                    return Ok(verdict)
+                }
+                _ => {}
+            }
+        }
+
+        let blocked_until = attempts
+            .iter()
+            .map(|a| a.at + a.verdict.retry_after().as_secs())
+            .max()
+            .unwrap_or(0);
+
+        if blocked_until > now() {
+            let wait = Duration::from_secs(blocked_until - now());
+            return Err(ClientError::RateLimited(wait));
+        }
+
+        let body = self
+            .agent
+            .post(&format!("{}/{year}/day/{day}/answer", self.base_url))
+            .set("Cookie", &self.cookie()?)
+            .send_form(&[("level", &part.to_string()), ("answer", &answer)])?
+            .into_string()?;
+
+        let verdict = Verdict::parse(&body)
+            .ok_or_else(|| ClientError::UnexpectedResponse(body.clone()))?;
+
+        self.record(&Attempt { year, day, part, at: now(), verdict, answer })?;
+
+        match verdict {
+            Verdict::Wait(wait) => Err(ClientError::RateLimited(wait)),
+            verdict => Ok(verdict),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TempDir;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+
+    // serves one canned body per request and reports each request line
+    fn serve(bodies: &[&str]) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let bodies =
+            bodies.iter().map(|body| body.to_string()).collect::<Vec<_>>();
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            for body in bodies {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request = String::new();
+                let mut length = 0;
+
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+
+                    if let Some(value) = line.strip_prefix("Content-Length: ")
+                    {
+                        length = value.trim().parse().unwrap();
+                    }
+                    if line == "\r\n" {
+                        break;
+                    }
+                    request.push_str(&line);
+                }
+
+                let mut form = vec![0; length];
+                reader.read_exact(&mut form).unwrap();
+                request.push_str(&String::from_utf8(form).unwrap());
+
+                tx.send(request).unwrap();
+                write!(
+                    reader.get_mut(),
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\
+                     Connection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+
+        (url, rx)
+    }
+
+    // the directory goes away with the guard, so keep it as long as the
+    // client
+    fn client(url: &str, test: &str) -> (Client, TempDir) {
+        let dir = TempDir::new(&format!("client-{test}"));
+        let client = Client::new(Some("token".to_string()))
+            .with_base_url(url)
+            .with_data_dir(dir.path());
+
+        (client, dir)
+    }
+
+    #[test]
+    fn inputs_are_cached() {
+        let (url, requests) = serve(&["1000\n2000\n"]);
+        let (client, _dir) = client(&url, "cache");
+
+        assert_eq!(client.input(2022, 1).unwrap(), "1000\n2000\n");
+        assert_eq!(client.input(2022, 1).unwrap(), "1000\n2000\n");
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("GET /2022/day/1/input HTTP/1.1"));
+        assert!(request.contains("Cookie: session=token"));
+        assert!(requests.try_recv().is_err());
+        assert!(client.input_path(2022, 1).ends_with("y2022/day01.txt"));
+    }
+
+    #[test]
+    fn missing_session() {
+        let dir = TempDir::new("client-missing-session");
+        let client = Client::new(None)
+            .with_base_url("http://127.0.0.1:9")
+            .with_data_dir(dir.path());
+
+        assert!(matches!(
+            client.input(2022, 1),
+            Err(ClientError::MissingSession)
+        ));
+    }
+
+    #[test]
+    fn submissions_are_recorded() {
+        let (url, requests) = serve(&[
+            "<article><p>That's not the right answer; your answer is too \
+             low. Please wait one minute before trying again.</p></article>",
+        ]);
+        let (client, _dir) = client(&url, "submit");
+
+        let verdict = client.submit(2022, 16, 1, &Answer::Integer(1700));
+        assert_eq!(verdict.unwrap(), Verdict::TooLow);
+        assert!(requests.recv().unwrap().ends_with("level=1&answer=1700"));
+
+        // known answers are not sent again
+        let verdict = client.submit(2022, 16, 1, &Answer::Integer(1700));
+        assert_eq!(verdict.unwrap(), Verdict::TooLow);
+
+        let verdict = client.submit(2022, 16, 1, &Answer::Integer(1751));
+        assert!(matches!(verdict, Err(ClientError::RateLimited(_))));
+
+        let history = client.history().unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].answer, "1700");
+        assert!(requests.try_recv().is_err());
+    }
+
+    #[test]
+    fn solved_parts_keep_their_answer() {
+        let (url, requests) = serve(&[
+            "<article><p>That's the right answer! You are one gold star \
+             closer to collecting enough star fruit.</p></article>",
+        ]);
+        let (client, _dir) = client(&url, "solved");
+        let render = Answer::render("#..#\n#\t.#\\");
+
+        let verdict = client.submit(2022, 10, 2, &render);
+        assert_eq!(verdict.unwrap(), Verdict::Correct);
+        assert!(requests.recv().is_ok());
+
+        let verdict = client.submit(2022, 10, 2, &render);
+        assert_eq!(verdict.unwrap(), Verdict::Correct);
+
+        let verdict = client.submit(2022, 10, 2, &Answer::from("PLULKBZH"));
+        assert!(matches!(
+            verdict,
+            Err(ClientError::SolvedWith(answer)) if answer == render.to_string()
+        ));
+
+        let history = fs::read_to_string(client.history_path()).unwrap();
+        assert_eq!(history.lines().count(), 1);
+        assert!(history.ends_with("\t#..#\\n#\\t.#\\\\\n"));
+        assert_eq!(client.history().unwrap()[0].answer, render.to_string());
+        assert!(requests.try_recv().is_err());
+    }
+
+    #[test]
+    fn verdicts() {
+        assert_eq!(
+            Verdict::parse("That's the right answer! You are one gold star"),
+            Some(Verdict::Correct)
+        );
+        assert_eq!(
+            Verdict::parse(
+                "You gave an answer too recently. You have 1m 32s left to wait."
+            ),
+            Some(Verdict::Wait(Duration::from_secs(92)))
+        );
+        assert_eq!(Verdict::parse("<html></html>"), None);
+        assert_eq!(parse_wait("You have 1m 3é left to wait."), None);
+        assert_eq!(
+            "wait_92".parse(),
+            Ok(Verdict::Wait(Duration::from_secs(92)))
+        );
+    }
+}
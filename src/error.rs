@@ -0,0 +1,101 @@
+use std::fmt;
+use std::num::ParseIntError;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub file: Option<String>,
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(expected: impl Into<String>) -> Self {
+        Self { file: None, line: 1, column: 1, expected: expected.into() }
+    }
+
+    pub fn at(self, line: usize, column: usize) -> Self {
+        Self { line, column, ..self }
+    }
+
+    pub fn at_column(self, column: usize) -> Self {
+        Self { column, ..self }
+    }
+
+    // moves a column relative to `token` to be relative to `line`, so
+    // `token` must be a slice borrowed from `line`
+    pub fn at_token(self, line: &str, token: &str) -> Self {
+        let offset = (token.as_ptr() as usize)
+            .checked_sub(line.as_ptr() as usize)
+            .filter(|&offset| line.is_char_boundary(offset))
+            .map_or(0, |offset| line[..offset].chars().count());
+
+        Self { column: self.column + offset, ..self }
+    }
+
+    pub fn below(self, lines: usize) -> Self {
+        Self { line: self.line + lines, ..self }
+    }
+
+    pub fn in_file(self, path: &str) -> Self {
+        Self { file: Some(path.to_string()), ..self }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.file {
+            Some(file) => write!(f, "{file}:{}:{}", self.line, self.column)?,
+            None => write!(f, "line {}, column {}", self.line, self.column)?,
+        }
+
+        write!(f, ": expected {}", self.expected)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<ParseIntError> for ParseError {
+    fn from(_: ParseIntError) -> Self {
+        Self::new("a number")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_position() {
+        let err = ParseError::new("a number").at(3, 7);
+
+        assert_eq!(err.to_string(), "line 3, column 7: expected a number");
+        assert_eq!(
+            err.in_file("data/y2022/day11.txt").to_string(),
+            "data/y2022/day11.txt:3:7: expected a number"
+        );
+    }
+
+    #[test]
+    fn token_column() {
+        let line = "move 3 from 1 to 2";
+        let token = line.split(' ').nth(2).unwrap();
+
+        assert_eq!(ParseError::new("to").at_token(line, token).column, 8);
+        assert_eq!(ParseError::new("to").at_token(line, "from").column, 1);
+        assert_eq!(
+            ParseError::new("a number")
+                .at_column(2)
+                .at_token(line, token)
+                .column,
+            9
+        );
+    }
+
+    #[test]
+    fn shifted_lines() {
+        let err = ParseError::new("a number").at(2, 4).below(10);
+
+        assert_eq!((err.line, err.column), (12, 4));
+    }
+}
@@ -0,0 +1,285 @@
+use crate::manifest::Manifest;
+use itertools::Itertools;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use toml::Value;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Example {
+    pub input: String,
+    pub answers: Vec<(usize, String)>,
+}
+
+fn decode(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html;
+
+    while let Some(start) = rest.find(['<', '&']) {
+        text.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        if rest.starts_with('<') {
+            rest = rest.find('>').map_or("", |end| &rest[end + 1..]);
+            continue;
+        }
+
+        let entity = rest.find(';').map_or(rest, |end| &rest[..=end]);
+        let decoded = match entity {
+            "&lt;" => "<",
+            "&gt;" => ">",
+            "&amp;" => "&",
+            "&quot;" => "\"",
+            "&#39;" | "&apos;" => "'",
+            _ => {
+                text.push('&');
+                rest = &rest[1..];
+                continue;
+            }
+        };
+
+        text.push_str(decoded);
+        rest = &rest[entity.len()..];
+    }
+
+    text.push_str(rest);
+    text
+}
+
+// yields the html between each `open` and the following `close`
+fn sections<'a>(
+    html: &'a str,
+    open: &'a str,
+    close: &'a str,
+) -> impl Iterator<Item = &'a str> {
+    html.split(open).skip(1).filter_map(move |s| Some(s.split_once(close)?.0))
+}
+
+// the example answer is the last emphasised code in each part description,
+// and belongs to the last example shown before it
+pub fn extract(html: &str) -> Vec<Example> {
+    let mut examples: Vec<Example> = Vec::new();
+
+    for (part, article) in sections(html, "<article", "</article>").enumerate()
+    {
+        for block in sections(article, "<pre><code>", "</code></pre>") {
+            let input = decode(block);
+
+            if !examples.iter().any(|e| e.input == input) {
+                examples.push(Example { input, answers: vec![] });
+            }
+        }
+
+        let answer =
+            sections(article, "<code><em>", "</em></code>").last().map(decode);
+        let shown = sections(article, "<pre><code>", "</code></pre>")
+            .last()
+            .map(decode);
+        let example = match shown {
+            Some(input) => examples.iter_mut().find(|e| e.input == input),
+            None => examples.last_mut(),
+        };
+
+        if let (Some(answer), Some(example)) = (answer, example) {
+            example.answers.push((part + 1, answer));
+        }
+    }
+
+    examples
+}
+
+pub fn file_name(day: u8, n: usize) -> String {
+    format!("day{day:02}-example{n}.txt")
+}
+
+// sections for the answers of examples not yet in the manifest
+pub fn manifest_entries(
+    manifest: &Manifest,
+    year: u16,
+    day: u8,
+    examples: &[Example],
+) -> String {
+    examples
+        .iter()
+        .enumerate()
+        .filter(|(_, example)| !example.answers.is_empty())
+        .map(|(i, example)| (file_name(day, i + 1), example))
+        .filter(|(file, _)| {
+            !manifest
+                .entries()
+                .iter()
+                .any(|e| e.year == year && e.day == day && &e.file == file)
+        })
+        .map(|(file, example)| {
+            let answers = example
+                .answers
+                .iter()
+                .sorted()
+                .map(|(part, answer)| match answer.parse::<i64>() {
+                    Ok(n) => format!("part{part} = {n}\n"),
+                    Err(_) => {
+                        let answer = Value::String(answer.clone());
+                        format!("part{part} = {answer}\n")
+                    }
+                })
+                .collect::<String>();
+
+            format!("[{year}.{day:02}.\"{file}\"]\n{answers}")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// writes each example next to the puzzle input, leaving identical files
+// untouched and refusing to replace different ones. every file is checked
+// before any is written, so a conflict leaves nothing new behind
+pub fn write(
+    dir: &Path,
+    day: u8,
+    examples: &[Example],
+) -> io::Result<Vec<PathBuf>> {
+    let mut missing = vec![];
+
+    for (i, example) in examples.iter().enumerate() {
+        let path = dir.join(file_name(day, i + 1));
+
+        match fs::read_to_string(&path) {
+            Ok(existing) if existing == example.input => {}
+            Ok(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::AlreadyExists,
+                    format!("{} has a different example", path.display()),
+                ))
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                missing.push((path, example));
+            }
+            Err(err) => return Err(err),
+        }
+    }
+
+    fs::create_dir_all(dir)?;
+
+    for (path, example) in &missing {
+        fs::write(path, &example.input)?;
+    }
+
+    Ok(missing.into_iter().map(|(path, _)| path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::Answer;
+    use crate::testing::TempDir;
+
+    const PAGE: &str = "<main>
+<article class=\"day-desc\"><h2>--- Day 1: Trebuchet?! ---</h2>
+<p>For example:</p>
+<pre><code>1abc2
+pqr3stu8vwx
+</code></pre>
+<p>Adding these together produces <code><em>142</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>54632</code>.</p>
+<article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2>
+<pre><code>two1nine
+<em>eight</em>wo&lt;three
+</code></pre>
+<p>Adding these together produces <code><em>281</em></code>.</p>
+</article>
+</main>";
+
+    #[test]
+    fn examples_and_answers() {
+        let examples = extract(PAGE);
+
+        assert_eq!(
+            examples,
+            vec![
+                Example {
+                    input: "1abc2\npqr3stu8vwx\n".to_string(),
+                    answers: vec![(1, "142".to_string())],
+                },
+                Example {
+                    input: "two1nine\neightwo<three\n".to_string(),
+                    answers: vec![(2, "281".to_string())],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn answers_reuse_the_previous_example() {
+        let page = PAGE.replace(
+            "<pre><code>two1nine\n<em>eight</em>wo&lt;three\n</code></pre>",
+            "",
+        );
+
+        let examples = extract(&page);
+
+        assert_eq!(examples.len(), 1);
+        assert_eq!(
+            examples[0].answers,
+            vec![(1, "142".to_string()), (2, "281".to_string())]
+        );
+    }
+
+    #[test]
+    fn new_manifest_entries() {
+        let manifest = Manifest::parse(
+            "[2023.01]\n\"day01-example1.txt\" = { part1 = 142 }\n",
+        )
+        .unwrap();
+        let mut examples = extract(PAGE);
+        examples[1].answers.push((1, "2=-1=0".to_string()));
+
+        let entries = manifest_entries(&manifest, 2023, 1, &examples);
+
+        assert_eq!(
+            entries,
+            "[2023.01.\"day01-example2.txt\"]\npart1 = \"2=-1=0\"\npart2 = 281\n"
+        );
+
+        let manifest = Manifest::parse(&entries).unwrap();
+        assert_eq!(manifest.entries()[0].answer, Answer::from("2=-1=0"));
+    }
+
+    #[test]
+    fn answers_are_toml_strings() {
+        let answers = ["say \"hi\"", "back\\slash", "tab\there", "ü\u{7f}"];
+        let examples = answers
+            .iter()
+            .map(|answer| Example {
+                input: "x\n".to_string(),
+                answers: vec![(1, answer.to_string())],
+            })
+            .collect::<Vec<_>>();
+
+        let entries =
+            manifest_entries(&Manifest::default(), 2023, 1, &examples);
+        let manifest = Manifest::parse(&entries).unwrap();
+
+        assert_eq!(
+            manifest.entries().iter().map(|e| &e.answer).collect::<Vec<_>>(),
+            answers.map(Answer::from).iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn conflicts_write_nothing() {
+        let dir = TempDir::new("examples-conflicts");
+        let examples = extract(PAGE);
+        let second = dir.path().join(file_name(1, 2));
+
+        fs::write(&second, "something else\n").unwrap();
+        let err = write(dir.path(), 1, &examples).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+        assert!(!dir.path().join(file_name(1, 1)).exists());
+
+        fs::write(&second, &examples[1].input).unwrap();
+        let written = write(dir.path(), 1, &examples).unwrap();
+        assert_eq!(written, [dir.path().join(file_name(1, 1))]);
+        assert!(write(dir.path(), 1, &examples).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,82 @@
+use crate::point::Point2;
+
+// polygons given by their corners in order, the last one joining back to
+// the first, in i64 so they can be far bigger than any grid we could fill
+
+const fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+fn edges(
+    vertices: &[Point2<i64>],
+) -> impl Iterator<Item = (Point2<i64>, Point2<i64>)> + '_ {
+    vertices.iter().zip(vertices.iter().cycle().skip(1)).map(|(&a, &b)| (a, b))
+}
+
+// twice the enclosed area, the shoelace formula, which keeps lattice
+// polygons in whole numbers
+pub fn double_area(vertices: &[Point2<i64>]) -> i64 {
+    edges(vertices).map(|(a, b)| a.x * b.y - b.x * a.y).sum::<i64>().abs()
+}
+
+// the lattice points on the edges
+pub fn boundary_points(vertices: &[Point2<i64>]) -> i64 {
+    edges(vertices).map(|(a, b)| gcd(b.x - a.x, b.y - a.y)).sum()
+}
+
+// the lattice points strictly inside, from pick's theorem
+// A = i + b / 2 - 1, which only holds for polygons enclosing something
+pub fn interior_points(vertices: &[Point2<i64>]) -> i64 {
+    let area = double_area(vertices);
+    if vertices.len() < 3 || area == 0 {
+        return 0;
+    }
+
+    (area - boundary_points(vertices)) / 2 + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn polygon(corners: &[(i64, i64)]) -> Vec<Point2<i64>> {
+        corners.iter().map(|&(x, y)| Point2::new(x, y)).collect()
+    }
+
+    #[test]
+    fn rectangle() {
+        let rectangle = polygon(&[(0, 0), (4, 0), (4, 3), (0, 3)]);
+
+        assert_eq!(double_area(&rectangle), 24);
+        assert_eq!(boundary_points(&rectangle), 14);
+        assert_eq!(interior_points(&rectangle), 6);
+    }
+
+    #[test]
+    fn triangles() {
+        let triangle = polygon(&[(0, 0), (0, 4), (4, 0)]);
+
+        assert_eq!(double_area(&triangle), 16);
+        assert_eq!(boundary_points(&triangle), 12);
+        assert_eq!(interior_points(&triangle), 3);
+
+        let huge = polygon(&[(0, 0), (0, 1 << 30), (1 << 30, 1 << 30)]);
+        assert_eq!(boundary_points(&huge), 3 << 30);
+        assert_eq!(double_area(&huge), 1 << 60);
+    }
+
+    #[test]
+    fn degenerate_polygons() {
+        assert_eq!(interior_points(&[]), 0);
+        assert_eq!(interior_points(&polygon(&[(2, 3)])), 0);
+        assert_eq!(interior_points(&polygon(&[(0, 0), (4, 0)])), 0);
+
+        let line = polygon(&[(0, 0), (2, 2), (4, 4)]);
+        assert_eq!(double_area(&line), 0);
+        assert_eq!(interior_points(&line), 0);
+    }
+}
@@ -1,116 +1,986 @@
-use std::collections::HashMap;
+use crate::error::ParseError;
+use crate::point::{Point, Point2};
+use crate::search::bfs;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
+use std::ops::{Index, IndexMut};
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
-pub struct Position2D {
-    pub x: i32,
-    pub y: i32,
-}
+pub type Position2D = Point2<i32>;
 
 impl Position2D {
-    pub const fn new(x: i32, y: i32) -> Self {
-        Self { x, y }
-    }
-
     pub const fn distance(&self, rhs: &Self) -> usize {
         ((self.x - rhs.x).abs() + (self.y - rhs.y).abs()) as usize
     }
 
+    // the neighbours without a negative coordinate, as most positions
+    // index grids starting at the origin, see the _within variants
     pub fn neighbours4(&self) -> Vec<Self> {
-        let neighbours: Vec<Self> = vec![
-            Self::new(1, 0),
-            Self::new(-1, 0),
-            Self::new(0, -1),
-            Self::new(0, 1),
-        ];
+        self.neighbours4_within(Boundary::NON_NEGATIVE)
+    }
+
+    pub fn neighbours8(&self) -> Vec<Self> {
+        self.neighbours8_within(Boundary::NON_NEGATIVE)
+    }
+
+    pub fn neighbours4_within(&self, boundary: Boundary) -> Vec<Self> {
+        Direction::CARDINAL
+            .iter()
+            .filter_map(|&d| boundary.resolve(*self + d))
+            .collect()
+    }
 
-        neighbours
+    pub fn neighbours8_within(&self, boundary: Boundary) -> Vec<Self> {
+        Direction::ALL
             .iter()
-            .filter_map(|n| {
-                let p = *n + *self;
-                if p.x >= 0 && p.y >= 0 {
-                    Some(p)
-                } else {
-                    None
+            .filter_map(|&d| boundary.resolve(*self + d))
+            .collect()
+    }
+}
+
+// what neighbour queries do with positions leaving a rectangle, both of
+// its corners included
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Boundary {
+    #[default]
+    Unbounded,
+    Clip {
+        min: Position2D,
+        max: Position2D,
+    },
+    Wrap {
+        min: Position2D,
+        max: Position2D,
+    },
+}
+
+impl Boundary {
+    pub const NON_NEGATIVE: Self = Self::Clip {
+        min: Position2D::new(0, 0),
+        max: Position2D::new(i32::MAX, i32::MAX),
+    };
+
+    pub fn resolve(&self, p: Position2D) -> Option<Position2D> {
+        match *self {
+            Self::Unbounded => Some(p),
+            Self::Clip { min, max } => {
+                let inside = (min.x..=max.x).contains(&p.x)
+                    && (min.y..=max.y).contains(&p.y);
+
+                inside.then_some(p)
+            }
+            Self::Wrap { min, max } => Some(Position2D::new(
+                min.x + (p.x - min.x).rem_euclid(max.x - min.x + 1),
+                min.y + (p.y - min.y).rem_euclid(max.y - min.y + 1),
+            )),
+        }
+    }
+}
+
+// screen directions, y growing downwards, declared clockwise from up
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction {
+    pub const CARDINAL: [Self; 4] =
+        [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    pub const ALL: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    // turns clockwise by eighths of a full turn
+    const fn rotate(self, eighths: usize) -> Self {
+        Self::ALL[(self as usize + eighths) % 8]
+    }
+
+    pub const fn turn_right(self) -> Self {
+        self.rotate(2)
+    }
+
+    pub const fn turn_left(self) -> Self {
+        self.rotate(6)
+    }
+
+    pub const fn turn_around(self) -> Self {
+        self.rotate(4)
+    }
+
+    pub const fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+
+    pub const fn to_offset(self) -> Position2D {
+        match self {
+            Self::Up => Position2D::new(0, -1),
+            Self::UpRight => Position2D::new(1, -1),
+            Self::Right => Position2D::new(1, 0),
+            Self::DownRight => Position2D::new(1, 1),
+            Self::Down => Position2D::new(0, 1),
+            Self::DownLeft => Position2D::new(-1, 1),
+            Self::Left => Position2D::new(-1, 0),
+            Self::UpLeft => Position2D::new(-1, -1),
+        }
+    }
+}
+
+const EXPECTED_DIRECTION: &str = "a direction (U, D, L, R, an arrow or NSEW)";
+
+// U/D/L/R, arrows or compass points, north being up
+impl TryFrom<char> for Direction {
+    type Error = ParseError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'U' | '^' | 'N' => Ok(Self::Up),
+            'D' | 'v' | 'S' => Ok(Self::Down),
+            'L' | '<' | 'W' => Ok(Self::Left),
+            'R' | '>' | 'E' => Ok(Self::Right),
+            _ => Err(ParseError::new(EXPECTED_DIRECTION)),
+        }
+    }
+}
+
+impl std::str::FromStr for Direction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "NE" => Ok(Self::UpRight),
+            "SE" => Ok(Self::DownRight),
+            "SW" => Ok(Self::DownLeft),
+            "NW" => Ok(Self::UpLeft),
+            _ => {
+                let mut chars = s.chars();
+
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Self::try_from(c),
+                    _ => Err(ParseError::new(EXPECTED_DIRECTION)),
                 }
-            })
-            .collect::<Vec<_>>()
+            }
+        }
     }
+}
 
-    pub fn neighbours8(&self) -> Vec<Self> {
-        let neighbours: Vec<Self> = vec![
-            Self::new(-1, -1),
-            Self::new(0, -1),
-            Self::new(1, -1),
-            Self::new(-1, 0),
-            Self::new(1, 0),
-            Self::new(-1, 1),
-            Self::new(0, 1),
-            Self::new(1, 1),
-        ];
+impl std::ops::Add<Direction> for Position2D {
+    type Output = Self;
+
+    fn add(self, direction: Direction) -> Self {
+        self + direction.to_offset()
+    }
+}
+
+impl std::ops::AddAssign<Direction> for Position2D {
+    fn add_assign(&mut self, direction: Direction) {
+        *self = *self + direction;
+    }
+}
+
+pub fn dimensions<V>(grid: &HashMap<Position2D, V>) -> (i32, i32) {
+    grid.keys()
+        .fold((i32::MIN, i32::MIN), |acc, p| (acc.0.max(p.x), acc.1.max(p.y)))
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self { width, height, cells: vec![fill; width * height] }
+    }
+
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, ParseError> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+        let mut cells = Vec::with_capacity(width * height);
+
+        for (y, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(ParseError::new(format!(
+                    "a row of width {width}"
+                ))
+                .at(y + 1, row.len().min(width) + 1));
+            }
 
-        neighbours
+            cells.extend(row);
+        }
+
+        Ok(Self { width, height, cells })
+    }
+
+    // pads rows shorter than the longest one with `fill`
+    pub fn from_rows_padded(rows: Vec<Vec<T>>, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        let height = rows.len();
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let mut cells = Vec::with_capacity(width * height);
+
+        for row in rows {
+            let padding = width - row.len();
+
+            cells.extend(row);
+            cells.extend(std::iter::repeat_n(fill.clone(), padding));
+        }
+
+        Self { width, height, cells }
+    }
+
+    // covers the map from the origin to its furthest position, positions
+    // with negative coordinates are left out
+    pub fn from_map(map: &HashMap<Position2D, T>, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        let (x_max, y_max) = dimensions(map);
+        let width = usize::try_from(x_max + 1).unwrap_or(0);
+        let height = usize::try_from(y_max + 1).unwrap_or(0);
+        let mut grid = Self::new(width, height, fill);
+
+        for (p, value) in map {
+            if let Some(cell) = grid.get_mut(*p) {
+                *cell = value.clone();
+            }
+        }
+
+        grid
+    }
+
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    fn offset(&self, p: Position2D) -> Option<usize> {
+        let x = usize::try_from(p.x).ok().filter(|&x| x < self.width)?;
+        let y = usize::try_from(p.y).ok().filter(|&y| y < self.height)?;
+
+        Some(y * self.width + x)
+    }
+
+    fn position(&self, offset: usize) -> Position2D {
+        Position2D::new(
+            (offset % self.width).try_into().expect("grid should fit in i32"),
+            (offset / self.width).try_into().expect("grid should fit in i32"),
+        )
+    }
+
+    pub fn contains(&self, p: Position2D) -> bool {
+        self.offset(p).is_some()
+    }
+
+    pub fn get(&self, p: Position2D) -> Option<&T> {
+        self.offset(p).map(|offset| &self.cells[offset])
+    }
+
+    pub fn get_mut(&mut self, p: Position2D) -> Option<&mut T> {
+        self.offset(p).map(|offset| &mut self.cells[offset])
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} out of bounds");
+
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Position2D> + '_ {
+        (0..self.cells.len()).map(|offset| self.position(offset))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Position2D, &T)> {
+        self.cells
             .iter()
-            .filter_map(|n| {
-                let p = *n + *self;
-                if p.x >= 0 && p.y >= 0 {
-                    Some(p)
-                } else {
-                    None
-                }
-            })
-            .collect::<Vec<_>>()
+            .enumerate()
+            .map(|(offset, value)| (self.position(offset), value))
+    }
+
+    pub fn bounds(&self) -> Boundary {
+        Boundary::Clip {
+            min: Position2D::new(0, 0),
+            max: Position2D::new(
+                self.width as i32 - 1,
+                self.height as i32 - 1,
+            ),
+        }
+    }
+
+    pub fn neighbours4(
+        &self,
+        p: Position2D,
+    ) -> impl Iterator<Item = Position2D> {
+        p.neighbours4_within(self.bounds()).into_iter()
+    }
+
+    pub fn neighbours8(
+        &self,
+        p: Position2D,
+    ) -> impl Iterator<Item = Position2D> {
+        p.neighbours8_within(self.bounds()).into_iter()
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    pub fn to_map(&self) -> HashMap<Position2D, T>
+    where
+        T: Clone,
+    {
+        self.iter().map(|(p, value)| (p, value.clone())).collect()
+    }
+
+    pub const fn view(&self, transform: Transform) -> View<'_, T> {
+        View { grid: self, transform }
+    }
+
+    // the 8 rotations and reflections of the grid, starting with itself
+    pub fn symmetries(&self) -> impl Iterator<Item = View<'_, T>> {
+        Transform::ALL.into_iter().map(|t| self.view(t))
+    }
+
+    pub fn transformed(&self, transform: Transform) -> Self
+    where
+        T: Clone,
+    {
+        self.view(transform).to_grid()
+    }
+
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.transformed(Transform::Transpose)
+    }
+
+    pub fn rotate_cw(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.transformed(Transform::RotateCw)
+    }
+
+    pub fn rotate_180(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.transformed(Transform::Rotate180)
+    }
+
+    pub fn rotate_ccw(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.transformed(Transform::RotateCcw)
+    }
+
+    pub fn flip_horizontal(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.transformed(Transform::FlipHorizontal)
+    }
+
+    pub fn flip_vertical(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.transformed(Transform::FlipVertical)
+    }
+}
+
+impl<T> Index<Position2D> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Position2D) -> &T {
+        self.get(p).unwrap_or_else(|| panic!("{p:?} out of bounds"))
+    }
+}
+
+impl<T> IndexMut<Position2D> for Grid<T> {
+    fn index_mut(&mut self, p: Position2D) -> &mut T {
+        self.get_mut(p).unwrap_or_else(|| panic!("{p:?} out of bounds"))
     }
 }
 
-impl fmt::Debug for Position2D {
+impl<T: fmt::Debug> fmt::Debug for Grid<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "({}, {})", self.x, self.y)
+        f.debug_list().entries(self.rows()).finish()
     }
 }
 
-impl std::ops::Add for Position2D {
-    type Output = Self;
+// the symmetries of a rectangle, flips being mirrors across the vertical
+// (horizontal flip) or horizontal (vertical flip) axis
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Transform {
+    Identity,
+    RotateCw,
+    Rotate180,
+    RotateCcw,
+    Transpose,
+    AntiTranspose,
+    FlipHorizontal,
+    FlipVertical,
+}
+
+impl Transform {
+    pub const ALL: [Self; 8] = [
+        Self::Identity,
+        Self::RotateCw,
+        Self::Rotate180,
+        Self::RotateCcw,
+        Self::Transpose,
+        Self::AntiTranspose,
+        Self::FlipHorizontal,
+        Self::FlipVertical,
+    ];
 
-    fn add(self, other: Self) -> Self {
-        Self { x: self.x + other.x, y: self.y + other.y }
+    const fn swaps_axes(self) -> bool {
+        matches!(
+            self,
+            Self::RotateCw
+                | Self::RotateCcw
+                | Self::Transpose
+                | Self::AntiTranspose
+        )
     }
 }
 
-impl std::ops::AddAssign for Position2D {
-    fn add_assign(&mut self, other: Self) {
-        *self = Self { x: self.x + other.x, y: self.y + other.y };
+// a transformed grid that reads from the original instead of copying it
+pub struct View<'a, T> {
+    grid: &'a Grid<T>,
+    transform: Transform,
+}
+
+impl<T> Clone for View<'_, T> {
+    fn clone(&self) -> Self {
+        *self
     }
 }
 
-impl std::ops::Sub for Position2D {
-    type Output = Self;
+impl<T> Copy for View<'_, T> {}
+
+impl<'a, T> View<'a, T> {
+    pub const fn width(&self) -> usize {
+        if self.transform.swaps_axes() {
+            self.grid.height
+        } else {
+            self.grid.width
+        }
+    }
+
+    pub const fn height(&self) -> usize {
+        if self.transform.swaps_axes() {
+            self.grid.width
+        } else {
+            self.grid.height
+        }
+    }
+
+    // the position in the underlying grid shown at `p`
+    fn source(&self, p: Position2D) -> Position2D {
+        let (w, h) = (self.grid.width as i32, self.grid.height as i32);
+        let (x, y) = (p.x, p.y);
+
+        let (x, y) = match self.transform {
+            Transform::Identity => (x, y),
+            Transform::RotateCw => (y, h - 1 - x),
+            Transform::Rotate180 => (w - 1 - x, h - 1 - y),
+            Transform::RotateCcw => (w - 1 - y, x),
+            Transform::Transpose => (y, x),
+            Transform::AntiTranspose => (w - 1 - y, h - 1 - x),
+            Transform::FlipHorizontal => (w - 1 - x, y),
+            Transform::FlipVertical => (x, h - 1 - y),
+        };
+
+        Position2D::new(x, y)
+    }
+
+    pub fn contains(&self, p: Position2D) -> bool {
+        (0..self.width() as i32).contains(&p.x)
+            && (0..self.height() as i32).contains(&p.y)
+    }
+
+    pub fn get(&self, p: Position2D) -> Option<&'a T> {
+        if self.contains(p) {
+            self.grid.get(self.source(p))
+        } else {
+            None
+        }
+    }
+
+    fn cell(self, x: usize, y: usize) -> &'a T {
+        &self.grid[self.source(Position2D::new(x as i32, y as i32))]
+    }
+
+    pub fn row(self, y: usize) -> impl Iterator<Item = &'a T> {
+        assert!(y < self.height(), "row {y} out of bounds");
+
+        (0..self.width()).map(move |x| self.cell(x, y))
+    }
+
+    pub fn rows(self) -> impl Iterator<Item = impl Iterator<Item = &'a T>> {
+        (0..self.height()).map(move |y| self.row(y))
+    }
+
+    pub fn column(self, x: usize) -> impl Iterator<Item = &'a T> {
+        assert!(x < self.width(), "column {x} out of bounds");
+
+        (0..self.height()).map(move |y| self.cell(x, y))
+    }
+
+    pub fn columns(self) -> impl Iterator<Item = impl Iterator<Item = &'a T>> {
+        (0..self.width()).map(move |x| self.column(x))
+    }
+
+    pub fn to_grid(self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width: self.width(),
+            height: self.height(),
+            cells: self.rows().flatten().cloned().collect(),
+        }
+    }
+}
+
+impl<T> Index<Position2D> for View<'_, T> {
+    type Output = T;
+
+    fn index(&self, p: Position2D) -> &T {
+        self.get(p).unwrap_or_else(|| panic!("{p:?} out of bounds"))
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for View<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list()
+            .entries(self.rows().map(|row| row.collect::<Vec<_>>()))
+            .finish()
+    }
+}
+
+// every point reachable from `seed` through orthogonal steps onto points
+// accepted by `passable`, the seed itself always included
+pub fn flood_fill<P: Point>(
+    seed: P,
+    passable: impl Fn(P) -> bool,
+) -> HashSet<P> {
+    bfs(seed, |p| {
+        p.orthogonal().into_iter().filter(|&n| passable(n)).collect()
+    })
+    .into_iter()
+    .collect()
+}
+
+pub fn bounding_box<P: Point>(
+    points: impl IntoIterator<Item = P>,
+) -> Option<(P, P)> {
+    points.into_iter().fold(None, |acc, p| match acc {
+        None => Some((p, p)),
+        Some((min, max)) => Some((min.lower(p), max.upper(p))),
+    })
+}
+
+// the points around `solid` that can be reached from outside of it, within
+// its bounding box grown by one so the fill can go all the way around
+pub fn exterior<P: Point>(solid: &HashSet<P>) -> HashSet<P> {
+    let Some((min, max)) = bounding_box(solid.iter().copied()) else {
+        return HashSet::new();
+    };
+    let (min, max) = (min.shift(-1), max.shift(1));
+
+    flood_fill(min, |p| p.within(&min, &max) && !solid.contains(&p))
+}
+
+// a set of orthogonally connected positions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    cells: HashSet<Position2D>,
+}
+
+impl Region {
+    pub const fn new(cells: HashSet<Position2D>) -> Self {
+        Self { cells }
+    }
+
+    pub const fn cells(&self) -> &HashSet<Position2D> {
+        &self.cells
+    }
+
+    pub fn contains(&self, p: Position2D) -> bool {
+        self.cells.contains(&p)
+    }
+
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
 
-    fn sub(self, other: Self) -> Self {
-        Self { x: self.x - other.x, y: self.y - other.y }
+    pub fn perimeter(&self) -> usize {
+        self.cells
+            .iter()
+            .flat_map(|p| p.neighbours4_within(Boundary::Unbounded))
+            .filter(|n| !self.contains(*n))
+            .count()
+    }
+
+    // a polygon has as many straight sides as corners, each cell adding
+    // its outer corners and the inner ones it sits in
+    pub fn sides(&self) -> usize {
+        self.cells
+            .iter()
+            .flat_map(|&p| {
+                Direction::CARDINAL.map(|d| {
+                    let (a, b) = (p + d, p + d.turn_right());
+                    let (a, b) = (self.contains(a), self.contains(b));
+                    let between = self.contains(p + d + d.turn_right());
+
+                    (!a && !b) || (a && b && !between)
+                })
+            })
+            .filter(|&corner| corner)
+            .count()
+    }
+
+    pub fn bounding_box(&self) -> Option<(Position2D, Position2D)> {
+        bounding_box(self.cells.iter().copied())
     }
 }
 
-pub fn print<V>(grid: &HashMap<Position2D, V>, default: &V)
-where
-    V: fmt::Display,
-{
-    let (x_min, x_max, y_min, y_max) = grid.keys().fold(
-        (i32::MAX, i32::MIN, i32::MAX, i32::MIN),
-        |acc, p| {
-            (acc.0.min(p.x), acc.1.max(p.x), acc.2.min(p.y), acc.3.max(p.y))
-        },
-    );
+impl<T> Grid<T> {
+    // the region of cells connected to `seed` whose values are accepted by
+    // `same`, which is empty when the seed is outside the grid
+    pub fn flood_fill(
+        &self,
+        seed: Position2D,
+        same: impl Fn(&T) -> bool,
+    ) -> Region {
+        if !self.contains(seed) {
+            return Region::new(HashSet::new());
+        }
+
+        Region::new(flood_fill(seed, |p| self.get(p).is_some_and(&same)))
+    }
+
+    // the connected areas of equal values, in reading order of their first
+    // cell
+    pub fn regions(&self) -> Vec<Region>
+    where
+        T: PartialEq,
+    {
+        let mut seen = HashSet::new();
+        let mut regions = vec![];
+
+        for (p, value) in self.iter() {
+            if seen.contains(&p) {
+                continue;
+            }
+
+            let region = self.flood_fill(p, |v| v == value);
+            seen.extend(region.cells().iter().copied());
+            regions.push(region);
+        }
+
+        regions
+    }
+
+    // the index in `regions` of the region each cell belongs to
+    pub fn labels(&self) -> Grid<usize>
+    where
+        T: PartialEq,
+    {
+        let mut labels = Grid::new(self.width, self.height, 0);
 
-    for y in y_min..=y_max {
-        for x in x_min..=x_max {
-            print!("{}", grid.get(&Position2D::new(x, y)).unwrap_or(default));
+        for (label, region) in self.regions().iter().enumerate() {
+            for &p in region.cells() {
+                labels[p] = label;
+            }
         }
-        println!();
+
+        labels
     }
 }
 
-pub fn dimensions<V>(grid: &HashMap<Position2D, V>) -> (i32, i32) {
-    grid.keys()
-        .fold((i32::MIN, i32::MIN), |acc, p| (acc.0.max(p.x), acc.1.max(p.y)))
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::point::Point3;
+
+    fn sample() -> Grid<u32> {
+        Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap()
+    }
+
+    #[test]
+    fn boundary_policies() {
+        let origin = Position2D::new(0, 0);
+        assert_eq!(origin.neighbours4().len(), 2);
+        assert_eq!(origin.neighbours8().len(), 3);
+        assert_eq!(origin.neighbours8_within(Boundary::Unbounded).len(), 8);
+
+        let clip = Boundary::Clip { min: origin, max: Position2D::new(2, 2) };
+        assert_eq!(origin.neighbours4_within(clip).len(), 2);
+        assert_eq!(Position2D::new(1, 1).neighbours8_within(clip).len(), 8);
+
+        let wrap = Boundary::Wrap {
+            min: Position2D::new(1, 1),
+            max: Position2D::new(3, 2),
+        };
+        assert_eq!(
+            wrap.resolve(Position2D::new(0, 3)),
+            Some(Position2D::new(3, 1))
+        );
+        assert!(Position2D::new(1, 1)
+            .neighbours4_within(wrap)
+            .contains(&Position2D::new(1, 2)));
+    }
+
+    #[test]
+    fn direction_turns() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.turn_around(), Direction::Right);
+        assert_eq!(Direction::UpRight.turn_right(), Direction::DownRight);
+        assert!(Direction::DownLeft.is_diagonal());
+
+        let offsets = Direction::ALL.map(Direction::to_offset);
+        assert_eq!(
+            offsets.iter().fold(Position2D::new(0, 0), |acc, &o| acc + o),
+            Position2D::new(0, 0)
+        );
+        assert_eq!(
+            Position2D::new(2, 2) + Direction::Up,
+            Position2D::new(2, 1)
+        );
+    }
+
+    #[test]
+    fn direction_alphabets() {
+        for (alphabet, expected) in [
+            ("URDL", Direction::CARDINAL),
+            ("^>v<", Direction::CARDINAL),
+            ("NESW", Direction::CARDINAL),
+        ] {
+            let parsed = alphabet
+                .chars()
+                .map(Direction::try_from)
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            assert_eq!(parsed, expected);
+        }
+
+        assert_eq!("SW".parse::<Direction>().unwrap(), Direction::DownLeft);
+        assert!("X".parse::<Direction>().is_err());
+        assert!("UU".parse::<Direction>().is_err());
+    }
+
+    #[test]
+    fn bounds_aware_indexing() {
+        let mut grid = sample();
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Position2D::new(2, 1)], 6);
+        assert_eq!(grid.get(Position2D::new(3, 0)), None);
+        assert_eq!(grid.get(Position2D::new(0, -1)), None);
+
+        grid[Position2D::new(0, 1)] = 9;
+        assert_eq!(grid.row(1), [9, 5, 6]);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = sample();
+
+        assert_eq!(grid.rows().collect::<Vec<_>>(), [[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), [2, 5]);
+        assert_eq!(grid.columns().count(), 3);
+        assert_eq!(
+            grid.iter().find(|(_, &v)| v == 5).map(|(p, _)| p),
+            Some(Position2D::new(1, 1))
+        );
+    }
+
+    #[test]
+    fn neighbours_in_bounds() {
+        let grid = sample();
+
+        let mut n =
+            grid.neighbours4(Position2D::new(0, 0)).collect::<Vec<_>>();
+        n.sort_by_key(|p| (p.y, p.x));
+        assert_eq!(n, [Position2D::new(1, 0), Position2D::new(0, 1)]);
+        assert_eq!(grid.neighbours8(Position2D::new(1, 1)).count(), 5);
+    }
+
+    #[test]
+    fn ragged_rows() {
+        let err = Grid::from_rows(vec![vec![1, 2], vec![3]]).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+
+        let grid = Grid::from_rows_padded(vec![vec![1, 2], vec![3]], 0);
+        assert_eq!(grid.row(1), [3, 0]);
+    }
+
+    #[test]
+    fn rotations_and_flips() {
+        let grid = sample();
+
+        assert_eq!(
+            grid.rotate_cw().rows().collect::<Vec<_>>(),
+            [[4, 1], [5, 2], [6, 3]]
+        );
+        assert_eq!(
+            grid.rotate_ccw().rows().collect::<Vec<_>>(),
+            [[3, 6], [2, 5], [1, 4]]
+        );
+        assert_eq!(grid.rotate_180().row(0), [6, 5, 4]);
+        assert_eq!(
+            grid.transpose().rows().collect::<Vec<_>>(),
+            [[1, 4], [2, 5], [3, 6]]
+        );
+        assert_eq!(grid.flip_horizontal().row(0), [3, 2, 1]);
+        assert_eq!(grid.flip_vertical().row(0), [4, 5, 6]);
+        assert_eq!(grid.rotate_cw().rotate_cw(), grid.rotate_180());
+        assert_eq!(grid.transpose().transpose(), grid);
+    }
+
+    #[test]
+    fn views_match_owned_transforms() {
+        let grid = sample();
+        let expected: [(Transform, &[&[u32]]); 8] = [
+            (Transform::Identity, &[&[1, 2, 3], &[4, 5, 6]]),
+            (Transform::RotateCw, &[&[4, 1], &[5, 2], &[6, 3]]),
+            (Transform::Rotate180, &[&[6, 5, 4], &[3, 2, 1]]),
+            (Transform::RotateCcw, &[&[3, 6], &[2, 5], &[1, 4]]),
+            (Transform::Transpose, &[&[1, 4], &[2, 5], &[3, 6]]),
+            (Transform::AntiTranspose, &[&[6, 3], &[5, 2], &[4, 1]]),
+            (Transform::FlipHorizontal, &[&[3, 2, 1], &[6, 5, 4]]),
+            (Transform::FlipVertical, &[&[4, 5, 6], &[1, 2, 3]]),
+        ];
+
+        for (transform, rows) in expected {
+            let view = grid.view(transform);
+            let owned = grid.transformed(transform);
+
+            assert_eq!(
+                (view.width(), view.height()),
+                (rows[0].len(), rows.len()),
+                "{transform:?}"
+            );
+            assert_eq!(
+                view.rows()
+                    .map(|row| row.copied().collect())
+                    .collect::<Vec<Vec<_>>>(),
+                rows,
+                "{transform:?}"
+            );
+            assert_eq!(
+                owned.rows().collect::<Vec<_>>(),
+                rows,
+                "{transform:?}"
+            );
+            assert_eq!(view.get(Position2D::new(0, 3)), None);
+        }
+
+        let distinct =
+            grid.symmetries().map(|v| v.to_grid()).collect::<HashSet<_>>();
+        assert_eq!(distinct.len(), 8);
+        assert_eq!(grid.view(Transform::RotateCw)[Position2D::new(1, 2)], 3);
+    }
+
+    #[test]
+    fn region_metrics() {
+        let garden = Grid::from_rows(
+            ["AAAA", "BBCD", "BBCC", "EEEC"]
+                .map(|row| row.chars().collect())
+                .to_vec(),
+        )
+        .unwrap();
+
+        let regions = garden.regions();
+        let metrics = regions
+            .iter()
+            .map(|r| (r.area(), r.perimeter(), r.sides()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            metrics,
+            [(4, 10, 4), (4, 8, 4), (4, 10, 8), (1, 4, 4), (3, 8, 4)]
+        );
+        assert_eq!(
+            regions[2].bounding_box(),
+            Some((Position2D::new(2, 1), Position2D::new(3, 3)))
+        );
+        assert_eq!(garden.labels().row(1), [1, 1, 2, 3]);
+    }
+
+    #[test]
+    fn exterior_fill() {
+        // a hollow 3x3x3 cube with a single air pocket in the middle
+        let solid = (0..27)
+            .map(|i| Point3::new(i % 3, i / 3 % 3, i / 9))
+            .filter(|&p| p != Point3::new(1, 1, 1))
+            .collect::<HashSet<_>>();
+
+        let outside = exterior(&solid);
+        assert_eq!(outside.len(), 5 * 5 * 5 - 27);
+        assert!(!outside.contains(&Point3::new(1, 1, 1)));
+
+        let ring = Position2D::new(1, 1)
+            .neighbours8()
+            .into_iter()
+            .collect::<HashSet<_>>();
+        assert_eq!(exterior(&ring).len(), 5 * 5 - 9);
+    }
+
+    #[test]
+    fn sparse_map_round_trip() {
+        let grid = sample();
+        let mut map = grid.to_map();
+
+        assert_eq!(map[&Position2D::new(2, 0)], 3);
+
+        map.remove(&Position2D::new(1, 1));
+        assert_eq!(Grid::from_map(&map, 0).row(1), [4, 0, 6]);
+    }
 }
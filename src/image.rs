@@ -0,0 +1,355 @@
+use crate::grid::{Grid, Position2D};
+use std::collections::HashMap;
+
+pub type Rgb = [u8; 3];
+
+// a picture of a grid or of one frame of a simulation, one pixel per cell
+// until scaled
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, background: Rgb) -> Self {
+        Self { width, height, pixels: vec![background; width * height] }
+    }
+
+    pub fn from_grid<T>(grid: &Grid<T>, palette: impl Fn(&T) -> Rgb) -> Self {
+        Self {
+            width: grid.width(),
+            height: grid.height(),
+            pixels: grid.rows().flatten().map(palette).collect(),
+        }
+    }
+
+    // the cells within the viewport, both corners included, so every frame
+    // of a growing simulation can share the same one
+    pub fn from_cells<T>(
+        cells: impl IntoIterator<Item = (Position2D, T)>,
+        (min, max): (Position2D, Position2D),
+        background: Rgb,
+        palette: impl Fn(&T) -> Rgb,
+    ) -> Self {
+        let width = usize::try_from(max.x - min.x + 1).unwrap_or(0);
+        let height = usize::try_from(max.y - min.y + 1).unwrap_or(0);
+        let mut image = Self::new(width, height, background);
+
+        for (p, value) in cells {
+            let (x, y) = (p.x - min.x, p.y - min.y);
+
+            if let (Ok(x), Ok(y)) = (usize::try_from(x), usize::try_from(y)) {
+                if x < width && y < height {
+                    image.pixels[y * width + x] = palette(&value);
+                }
+            }
+        }
+
+        image
+    }
+
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> Rgb {
+        self.pixels[y * self.width + x]
+    }
+
+    // every cell becomes a `factor` by `factor` square
+    pub fn scale(&self, factor: usize) -> Self {
+        let pixels = (0..self.height * factor)
+            .flat_map(|y| {
+                (0..self.width * factor)
+                    .map(move |x| self.pixel(x / factor, y / factor))
+            })
+            .collect();
+
+        Self {
+            width: self.width * factor,
+            height: self.height * factor,
+            pixels,
+        }
+    }
+
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut ppm =
+            format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        ppm.extend(self.pixels.iter().flatten());
+        ppm
+    }
+
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut header = vec![];
+        header.extend(u32::try_from(self.width).unwrap().to_be_bytes());
+        header.extend(u32::try_from(self.height).unwrap().to_be_bytes());
+        // 8 bits per channel, rgb, no interlacing
+        header.extend([8, 2, 0, 0, 0]);
+
+        let scanlines = self
+            .pixels
+            .chunks(self.width.max(1))
+            .flat_map(|row| {
+                // no filter
+                std::iter::once(0).chain(row.iter().flatten().copied())
+            })
+            .collect::<Vec<_>>();
+
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        png_chunk(&mut png, b"IHDR", &header);
+        png_chunk(&mut png, b"IDAT", &zlib_stored(&scanlines));
+        png_chunk(&mut png, b"IEND", &[]);
+        png
+    }
+}
+
+pub fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0, |crc, &byte| {
+        (0..8).fold(crc ^ u32::from(byte), |crc, _| {
+            (crc >> 1) ^ (0xedb8_8320 & 0u32.wrapping_sub(crc & 1))
+        })
+    })
+}
+
+fn png_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    let start = png.len() + 4;
+
+    png.extend(u32::try_from(data.len()).unwrap().to_be_bytes());
+    png.extend(kind);
+    png.extend(data);
+    png.extend(crc32(&png[start..]).to_be_bytes());
+}
+
+// a zlib stream of uncompressed deflate blocks, which every reader accepts
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut zlib = vec![0x78, 0x01];
+    let blocks = data.chunks(0xffff).collect::<Vec<_>>();
+
+    for (i, block) in blocks.iter().enumerate() {
+        let len = u16::try_from(block.len()).unwrap();
+
+        zlib.push(u8::from(i + 1 == blocks.len()));
+        zlib.extend(len.to_le_bytes());
+        zlib.extend((!len).to_le_bytes());
+        zlib.extend(*block);
+    }
+
+    if blocks.is_empty() {
+        zlib.extend([1, 0, 0, 0xff, 0xff]);
+    }
+
+    let (a, b) = data.iter().fold((1u32, 0u32), |(a, b), &byte| {
+        let a = (a + u32::from(byte)) % 65521;
+        (a, (b + a) % 65521)
+    });
+    zlib.extend(((b << 16) | a).to_be_bytes());
+    zlib
+}
+
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    bits: u8,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, size: u8) {
+        self.buffer |= u32::from(code) << self.bits;
+        self.bits += size;
+
+        while self.bits >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+// variable width lzw as gif uses it, starting over once 12 bit codes run
+// out
+fn lzw(indices: &[u8], min_size: u8) -> Vec<u8> {
+    let clear = 1u16 << min_size;
+    let end = clear + 1;
+
+    let mut writer = BitWriter::default();
+    let mut table = HashMap::new();
+    let mut size = min_size + 1;
+    let mut next = end + 1;
+
+    writer.write(clear, size);
+
+    let mut indices = indices.iter().copied();
+    let Some(first) = indices.next() else {
+        writer.write(end, size);
+        return writer.finish();
+    };
+    let mut prefix = u16::from(first);
+
+    for index in indices {
+        if let Some(&code) = table.get(&(prefix, index)) {
+            prefix = code;
+            continue;
+        }
+
+        writer.write(prefix, size);
+
+        if next < 4096 {
+            table.insert((prefix, index), next);
+            next += 1;
+            if next > 1 << size && size < 12 {
+                size += 1;
+            }
+        } else {
+            writer.write(clear, size);
+            table.clear();
+            size = min_size + 1;
+            next = end + 1;
+        }
+
+        prefix = u16::from(index);
+    }
+
+    writer.write(prefix, size);
+    if next == 1 << size && size < 12 {
+        size += 1;
+    }
+    writer.write(end, size);
+    writer.finish()
+}
+
+// an endlessly looping animation, `delay` being the time between frames in
+// hundredths of a second, None when the frames differ in size or have more
+// than 256 colours between them
+pub fn gif(frames: &[Image], delay: u16) -> Option<Vec<u8>> {
+    let first = frames.first()?;
+    if frames
+        .iter()
+        .any(|f| (f.width, f.height) != (first.width, first.height))
+    {
+        return None;
+    }
+
+    let mut colours = frames
+        .iter()
+        .flat_map(|f| f.pixels.iter().copied())
+        .collect::<Vec<_>>();
+    colours.sort_unstable();
+    colours.dedup();
+    if colours.len() > 256 {
+        return None;
+    }
+
+    // a table of 2^(bits) colours, at least 2 bits for the lzw codes
+    let bits = (1..=8).find(|&b| 1 << b >= colours.len()).unwrap_or(1).max(2);
+    let index = colours
+        .iter()
+        .enumerate()
+        .map(|(i, &c)| (c, i as u8))
+        .collect::<HashMap<_, _>>();
+
+    let width = u16::try_from(first.width).ok()?.to_le_bytes();
+    let height = u16::try_from(first.height).ok()?.to_le_bytes();
+
+    let mut gif = b"GIF89a".to_vec();
+    gif.extend(width);
+    gif.extend(height);
+    gif.extend([0xf0 | (bits - 1), 0, 0]);
+    gif.extend(colours.iter().flatten());
+    gif.extend(std::iter::repeat_n(0, 3 * ((1 << bits) - colours.len())));
+    gif.extend(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00");
+
+    for frame in frames {
+        gif.extend([0x21, 0xf9, 4, 0]);
+        gif.extend(delay.to_le_bytes());
+        gif.extend([0, 0]);
+
+        gif.extend([0x2c, 0, 0, 0, 0]);
+        gif.extend(width);
+        gif.extend(height);
+        gif.extend([0, bits]);
+
+        let indices =
+            frame.pixels.iter().map(|c| index[c]).collect::<Vec<_>>();
+        for block in lzw(&indices, bits).chunks(255) {
+            gif.push(block.len() as u8);
+            gif.extend(block);
+        }
+        gif.push(0);
+    }
+
+    gif.push(0x3b);
+    Some(gif)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLACK: Rgb = [0, 0, 0];
+    const WHITE: Rgb = [255, 255, 255];
+
+    fn checkers() -> Image {
+        let rows = (0..3).map(|y| (0..4).map(|x| (x + y) % 2).collect());
+        let grid = Grid::from_rows(rows.collect()).unwrap();
+
+        Image::from_grid(&grid, |&c| if c == 0 { BLACK } else { WHITE })
+    }
+
+    #[test]
+    fn crc_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+    }
+
+    #[test]
+    fn ppm_still() {
+        let ppm = checkers().to_ppm();
+
+        assert!(ppm.starts_with(b"P6\n4 3\n255\n"));
+        assert_eq!(ppm.len(), 11 + 4 * 3 * 3);
+        assert_eq!(crc32(&ppm), 0xb7ef_917c);
+    }
+
+    #[test]
+    fn png_still() {
+        let png = checkers().scale(2).to_png();
+
+        assert!(png.starts_with(
+            b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR\0\0\0\x08\0\0\0\x06"
+        ));
+        assert!(png.ends_with(b"IEND\xae\x42\x60\x82"));
+        assert_eq!(crc32(&png), 0x9698_3821);
+    }
+
+    #[test]
+    fn animated_gif() {
+        let viewport = (Position2D::new(-1, -1), Position2D::new(1, 1));
+        let frames = (0..4)
+            .map(|i| {
+                let cell = (Position2D::new(i % 3 - 1, 0), ());
+                Image::from_cells([cell], viewport, BLACK, |_| WHITE)
+            })
+            .collect::<Vec<_>>();
+
+        let gif = gif(&frames, 10).unwrap();
+
+        assert!(gif.starts_with(b"GIF89a\x03\0\x03\0"));
+        assert_eq!(gif.last(), Some(&0x3b));
+        assert_eq!(crc32(&gif), 0x1246_63ec);
+
+        assert_eq!(super::gif(&[checkers(), frames[0].clone()], 10), None);
+    }
+}
@@ -1,32 +1,59 @@
-use crate::grid::Position2D;
+use crate::error::ParseError;
+use crate::grid::{Grid, Position2D};
+use crate::point::{Point2, Scalar};
 use std::collections::HashMap;
 use std::str;
 
-pub fn read_value_per_line<T>(path: &str) -> Vec<T>
+pub fn read_file(path: &str) -> String {
+    std::fs::read_to_string(path).expect("should be able to read file")
+}
+
+pub fn read_value_per_line<T>(input: &str) -> Vec<T>
+where
+    T: str::FromStr,
+{
+    input.lines().filter_map(|line| line.parse::<T>().ok()).collect()
+}
+
+pub fn parse_value_per_line<T>(input: &str) -> Result<Vec<T>, ParseError>
 where
     T: str::FromStr,
+    T::Err: Into<ParseError>,
+{
+    parse_lines(input, |line| line.parse::<T>().map_err(Into::into))
+}
+
+pub fn parse_lines<T, F>(input: &str, mut f: F) -> Result<Vec<T>, ParseError>
+where
+    F: FnMut(&str) -> Result<T, ParseError>,
 {
-    std::fs::read_to_string(path)
-        .expect("should be able to read file")
+    input
         .lines()
-        .filter_map(|line| line.parse::<T>().ok())
+        .enumerate()
+        .map(|(y, line)| f(line).map_err(|err| err.below(y)))
         .collect()
 }
 
-pub fn read_grid<T>(path: &str) -> HashMap<Position2D, T>
+pub fn parse_token<T>(line: &str, token: &str) -> Result<T, ParseError>
 where
+    T: str::FromStr,
+    T::Err: Into<ParseError>,
+{
+    token.parse::<T>().map_err(|err| err.into().at_token(line, token))
+}
+
+pub fn read_grid<S, T>(input: &str) -> HashMap<Point2<S>, T>
+where
+    S: Scalar + TryFrom<usize>,
     T: TryFrom<char>,
 {
-    std::fs::read_to_string(path)
-        .expect("expected file")
+    input
         .lines()
         .enumerate()
         .flat_map(|(y, line)| {
             line.chars().enumerate().filter_map(move |(x, char)| {
-                let pos = Position2D {
-                    x: x.try_into().ok()?,
-                    y: y.try_into().ok()?,
-                };
+                let pos =
+                    Point2 { x: x.try_into().ok()?, y: y.try_into().ok()? };
 
                 T::try_from(char).ok().map(|v| (pos, v))
             })
@@ -34,15 +61,227 @@ where
         .collect()
 }
 
-pub fn read_value_chunks<T>(path: &str) -> Vec<Vec<T>>
+pub fn parse_grid<S, T>(
+    input: &str,
+) -> Result<HashMap<Point2<S>, T>, ParseError>
+where
+    S: Scalar + TryFrom<usize>,
+    T: TryFrom<char>,
+    T::Error: Into<ParseError>,
+{
+    input
+        .lines()
+        .enumerate()
+        .flat_map(|(y, line)| {
+            line.chars().enumerate().map(move |(x, char)| {
+                let value = T::try_from(char)
+                    .map_err(|err| err.into().at(y + 1, x + 1))?;
+
+                Ok((position(x, y), value))
+            })
+        })
+        .collect()
+}
+
+fn position<S>(x: usize, y: usize) -> Point2<S>
+where
+    S: Scalar + TryFrom<usize>,
+{
+    Point2 {
+        x: S::try_from(x).unwrap_or_else(|_| panic!("{x} too wide")),
+        y: S::try_from(y).unwrap_or_else(|_| panic!("{y} too high")),
+    }
+}
+
+// the characters next to a cell in the order of Direction::CARDINAL, None
+// past the edges
+pub type Around = [Option<char>; 4];
+
+// a grid along with the position of each marker
+pub type Marked<T> = (Grid<T>, HashMap<char, Position2D>);
+
+// a dense grid of cells like Grid::from_rows over parse_row, also finding
+// where each of the marker characters is, which must appear exactly once.
+// a marker cell decodes as the character `substitute` picks for it from
+// the ones around it, like the ground under a start or the pipe it hides.
+// a missing marker is reported just past the last line
+pub fn parse_marked_grid<T>(
+    input: &str,
+    markers: &[char],
+    substitute: impl Fn(char, Around) -> char,
+) -> Result<Marked<T>, ParseError>
+where
+    T: TryFrom<char>,
+    T::Error: Into<ParseError>,
+{
+    let rows = input
+        .lines()
+        .map(|line| line.chars().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let at = |x: usize, y: usize| rows.get(y)?.get(x).copied();
+
+    let mut cells = Vec::with_capacity(rows.len());
+    let mut found = HashMap::new();
+
+    for (y, row) in rows.iter().enumerate() {
+        let mut values = Vec::with_capacity(row.len());
+
+        for (x, &char) in row.iter().enumerate() {
+            let pos = position(x, y);
+
+            let char = if markers.contains(&char) {
+                if found.insert(char, pos).is_some() {
+                    let err = format!("a single marker ({char})");
+                    return Err(ParseError::new(err).at(y + 1, x + 1));
+                }
+
+                let (up, left) = (y.wrapping_sub(1), x.wrapping_sub(1));
+                substitute(
+                    char,
+                    [at(x, up), at(x + 1, y), at(x, y + 1), at(left, y)],
+                )
+            } else {
+                char
+            };
+
+            let value = T::try_from(char)
+                .map_err(|err| err.into().at(y + 1, x + 1))?;
+            values.push(value);
+        }
+
+        cells.push(values);
+    }
+
+    if let Some(missing) = markers.iter().find(|c| !found.contains_key(c)) {
+        return Err(ParseError::new(format!("a marker ({missing})"))
+            .at(rows.len() + 1, 1));
+    }
+
+    Ok((Grid::from_rows(cells)?, found))
+}
+
+pub fn parse_row<T>(line: &str) -> Result<Vec<T>, ParseError>
+where
+    T: TryFrom<char>,
+    T::Error: Into<ParseError>,
+{
+    line.chars()
+        .enumerate()
+        .map(|(x, char)| {
+            T::try_from(char).map_err(|err| err.into().at_column(x + 1))
+        })
+        .collect()
+}
+
+pub fn read_value_chunks<T>(input: &str) -> Vec<Vec<T>>
 where
     T: str::FromStr,
 {
-    std::fs::read_to_string(path)
-        .expect("expected file")
+    input
         .split("\n\n")
         .map(|chunk| {
             chunk.lines().filter_map(|line| line.parse::<T>().ok()).collect()
         })
         .collect()
 }
+
+pub fn parse_value_chunks<T>(input: &str) -> Result<Vec<Vec<T>>, ParseError>
+where
+    T: str::FromStr,
+    T::Err: Into<ParseError>,
+{
+    parse_chunks(input, parse_value_per_line)
+}
+
+pub fn parse_chunks<T, F>(input: &str, mut f: F) -> Result<Vec<T>, ParseError>
+where
+    F: FnMut(&str) -> Result<T, ParseError>,
+{
+    let mut offset = 0;
+
+    input
+        .trim_end()
+        .split("\n\n")
+        .map(|chunk| {
+            let value = f(chunk).map_err(|err| err.below(offset));
+            offset += chunk.lines().count() + 1;
+
+            value
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Direction;
+
+    #[test]
+    fn lenient_lines_skip_bad_values() {
+        assert_eq!(read_value_per_line::<u32>("1\nx\n3\n"), vec![1, 3]);
+    }
+
+    #[test]
+    fn strict_lines_report_bad_values() {
+        let err = parse_value_per_line::<u32>("1\nx\n3\n").unwrap_err();
+
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.expected, "a number");
+    }
+
+    #[test]
+    fn token_errors_report_column() {
+        let err = parse_lines("1,2\n3,x\n", |line| {
+            line.split(',')
+                .map(|v| parse_token::<u32>(line, v))
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .unwrap_err();
+
+        assert_eq!((err.line, err.column), (2, 3));
+    }
+
+    #[test]
+    fn marked_grids() {
+        let parse = |input| {
+            parse_marked_grid::<Direction>(input, &['S'], |_, around| {
+                assert_eq!(around, [Some('^'), Some('<'), None, None]);
+                'v'
+            })
+        };
+
+        let (grid, markers) = parse("^>\nS<\n").unwrap();
+        assert_eq!(markers[&'S'], Point2::new(0, 1));
+        assert_eq!(grid[Point2::new(0, 1)], Direction::Down);
+        assert_eq!((grid.width(), grid.height()), (2, 2));
+
+        let err = parse("^>\n").unwrap_err();
+        assert_eq!(err.expected, "a marker (S)");
+        assert_eq!((err.line, err.column), (2, 1));
+
+        let err =
+            parse_marked_grid::<Direction>("^>\nS<\n>S\n", &['S'], |_, _| 'v')
+                .unwrap_err();
+        assert_eq!(err.expected, "a single marker (S)");
+        assert_eq!((err.line, err.column), (3, 2));
+
+        // rows of different widths
+        let err =
+            parse_marked_grid::<Direction>("^>\nS\n", &['S'], |_, _| 'v')
+                .unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+    }
+
+    #[test]
+    fn strict_chunks_report_line() {
+        assert_eq!(
+            parse_value_chunks::<u32>("1\n2\n\n3\n\n4\n").unwrap(),
+            vec![vec![1, 2], vec![3], vec![4]]
+        );
+
+        let err =
+            parse_value_chunks::<u32>("1\n2\n\n3\n\n4\n5x\n").unwrap_err();
+
+        assert_eq!(err.line, 7);
+    }
+}
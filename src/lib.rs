@@ -1,7 +1,29 @@
+pub mod bench;
+pub mod client;
+mod bitgrid;
 mod convert;
+pub mod error;
+pub mod examples;
+mod geometry;
 mod grid;
+mod image;
 mod io;
+pub mod manifest;
+mod ocr;
+mod point;
+pub mod profile;
+pub mod registry;
+mod render;
+pub mod scaffold;
 mod search;
+pub mod solution;
+#[cfg(test)]
+mod testing;
 mod y2019;
 mod y2022;
 mod y2023;
+
+#[cfg(test)]
+mod answers {
+    include!(concat!(env!("OUT_DIR"), "/answers.rs"));
+}
@@ -0,0 +1,661 @@
+use advent_of_code_r::bench::{self, Baseline};
+use advent_of_code_r::client::Client;
+use advent_of_code_r::examples;
+use advent_of_code_r::manifest::{self, Manifest};
+use advent_of_code_r::profile::{self, Profile};
+use advent_of_code_r::registry;
+use advent_of_code_r::scaffold;
+use advent_of_code_r::solution::Answer;
+use std::io;
+use std::path::Path;
+use std::process::ExitCode;
+
+const USAGE: &str = "usage: aoc [--profile <name>] <command>
+       aoc <year> <day> [--part <1|2>] [--input <path>]
+       aoc check [<year> [<day>]] [--ignored]
+       aoc bench [<year> [<day>]] [--iterations <n>] [--json]
+                 [--baseline <path>] [--threshold <percent>] [--save <path>]
+       aoc new <year> <day>
+       aoc examples <year> <day> <page.html>
+       aoc fetch <year> <day>
+       aoc submit <year> <day> <part> [<answer>]";
+
+struct Args {
+    year: u16,
+    day: u8,
+    part: Option<usize>,
+    input: Option<String>,
+}
+
+#[derive(Default)]
+struct Filter {
+    year: Option<u16>,
+    day: Option<u8>,
+}
+
+impl Filter {
+    fn push(&mut self, arg: &str) -> Result<(), String> {
+        if self.year.is_none() {
+            self.year = Some(arg.parse().map_err(|_| "invalid year")?);
+        } else if self.day.is_none() {
+            self.day = Some(arg.parse().map_err(|_| "invalid day")?);
+        } else {
+            return Err(format!("unexpected argument: {arg}"));
+        }
+
+        Ok(())
+    }
+
+    fn matches(&self, year: u16, day: u8) -> bool {
+        self.year.is_none_or(|y| y == year)
+            && self.day.is_none_or(|d| d == day)
+    }
+}
+
+struct Check {
+    filter: Filter,
+    ignored: bool,
+}
+
+fn parse_check(args: impl Iterator<Item = String>) -> Result<Check, String> {
+    let mut check = Check { filter: Filter::default(), ignored: false };
+
+    for arg in args {
+        if arg == "--ignored" {
+            check.ignored = true;
+        } else {
+            check.filter.push(&arg)?;
+        }
+    }
+
+    Ok(check)
+}
+
+struct Bench {
+    filter: Filter,
+    iterations: usize,
+    json: bool,
+    baseline: Option<String>,
+    threshold: f64,
+    save: Option<String>,
+}
+
+fn parse_bench(
+    mut args: impl Iterator<Item = String>,
+) -> Result<Bench, String> {
+    let mut bench = Bench {
+        filter: Filter::default(),
+        iterations: 10,
+        json: false,
+        baseline: None,
+        threshold: 10.0,
+        save: None,
+    };
+
+    while let Some(arg) = args.next() {
+        let mut value =
+            |flag| args.next().ok_or(format!("missing value for {flag}"));
+
+        match arg.as_str() {
+            "--iterations" | "-n" => {
+                bench.iterations = value("--iterations")?
+                    .parse()
+                    .ok()
+                    .filter(|&n| n > 0)
+                    .ok_or("invalid iterations")?;
+            }
+            "--json" => bench.json = true,
+            "--baseline" => bench.baseline = Some(value("--baseline")?),
+            "--threshold" => {
+                bench.threshold = value("--threshold")?
+                    .parse()
+                    .map_err(|_| "invalid threshold")?;
+            }
+            "--save" => bench.save = Some(value("--save")?),
+            _ => bench.filter.push(&arg)?,
+        }
+    }
+
+    Ok(bench)
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let (year, day) = parse_puzzle(&mut args)?;
+
+    let mut part = None;
+    let mut input = None;
+
+    while let Some(flag) = args.next() {
+        match flag.as_str() {
+            "--part" | "-p" => {
+                let value = args.next().ok_or("missing value for --part")?;
+                part = Some(value.parse().map_err(|_| "invalid part")?);
+            }
+            "--input" | "-i" => {
+                input = Some(args.next().ok_or("missing value for --input")?);
+            }
+            _ => return Err(format!("unexpected argument: {flag}")),
+        }
+    }
+
+    Ok(Args { year, day, part, input })
+}
+
+struct Submit {
+    year: u16,
+    day: u8,
+    part: usize,
+    answer: Option<String>,
+}
+
+fn parse_puzzle(
+    args: &mut impl Iterator<Item = String>,
+) -> Result<(u16, u8), String> {
+    let year = args
+        .next()
+        .ok_or("missing year")?
+        .parse()
+        .map_err(|_| "invalid year")?;
+    let day = args
+        .next()
+        .ok_or("missing day")?
+        .parse()
+        .map_err(|_| "invalid day")?;
+
+    Ok((year, day))
+}
+
+fn parse_day(
+    mut args: impl Iterator<Item = String>,
+) -> Result<(u16, u8), String> {
+    let puzzle = parse_puzzle(&mut args)?;
+
+    match args.next() {
+        Some(arg) => Err(format!("unexpected argument: {arg}")),
+        None => Ok(puzzle),
+    }
+}
+
+fn parse_submit(
+    mut args: impl Iterator<Item = String>,
+) -> Result<Submit, String> {
+    let (year, day) = parse_puzzle(&mut args)?;
+    let part = args
+        .next()
+        .ok_or("missing part")?
+        .parse()
+        .map_err(|_| "invalid part")?;
+    let answer = args.next();
+
+    if let Some(arg) = args.next() {
+        return Err(format!("unexpected argument: {arg}"));
+    }
+
+    Ok(Submit { year, day, part, answer })
+}
+
+fn parse_examples(
+    mut args: impl Iterator<Item = String>,
+) -> Result<(u16, u8, String), String> {
+    let (year, day) = parse_puzzle(&mut args)?;
+    let page = args.next().ok_or("missing puzzle page")?;
+
+    match args.next() {
+        Some(arg) => Err(format!("unexpected argument: {arg}")),
+        None => Ok((year, day, page)),
+    }
+}
+
+fn read_input(path: &str) -> io::Result<String> {
+    if path == "-" {
+        io::read_to_string(io::stdin())
+    } else {
+        std::fs::read_to_string(path)
+    }
+}
+
+// takes `--profile <name>` out of the arguments of any command
+fn split_profile(
+    args: impl Iterator<Item = String>,
+) -> Result<(Option<Profile>, Vec<String>), String> {
+    let mut args = args.collect::<Vec<_>>();
+
+    let Some(at) = args.iter().position(|arg| arg == "--profile") else {
+        return Ok((None, args));
+    };
+
+    let name = args.get(at + 1).ok_or("missing value for --profile")?;
+    let profile = Profile::named(name);
+    args.drain(at..at + 2);
+
+    Ok((Some(profile), args))
+}
+
+fn load_manifest(profile: &Profile) -> Result<Manifest, String> {
+    let path = profile.manifest();
+
+    match std::fs::read_to_string(&path) {
+        Ok(text) => Manifest::parse(&text)
+            .map(|manifest| manifest.for_profile(profile))
+            .map_err(|err| err.in_file(&path).to_string()),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            Ok(Manifest::default())
+        }
+        Err(err) => Err(format!("cannot read {path}: {err}")),
+    }
+}
+
+// checks every profile unless one is given
+fn check(check: Check, profile: Option<Profile>) -> ExitCode {
+    let profiles =
+        match profile.map_or_else(profile::profiles, |p| Ok(vec![p])) {
+            Ok(profiles) => profiles,
+            Err(err) => {
+                eprintln!("cannot list profiles: {err}");
+                return ExitCode::FAILURE;
+            }
+        };
+
+    let mut entries = vec![];
+
+    for profile in &profiles {
+        match load_manifest(profile) {
+            Ok(manifest) => entries.extend_from_slice(manifest.entries()),
+            Err(err) => {
+                eprintln!("{err}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let (mut passed, mut failed, mut ignored) = (0, 0, 0);
+
+    for entry in &entries {
+        if !check.filter.matches(entry.year, entry.day) {
+            continue;
+        }
+
+        let name = format!("{} part {}", entry.input(), entry.part);
+
+        if let Some(reason) = entry.ignore.as_ref().filter(|_| !check.ignored)
+        {
+            println!("{name}: ignored ({reason})");
+            ignored += 1;
+            continue;
+        }
+
+        match manifest::solve(entry) {
+            Ok(answer) if answer == entry.answer => {
+                println!("{name}: ok");
+                passed += 1;
+            }
+            Ok(answer) => {
+                println!(
+                    "{name}: FAILED\nexpected: {}\n     got: {answer}",
+                    entry.answer
+                );
+                failed += 1;
+            }
+            Err(err) => {
+                println!("{name}: FAILED\n{}", err.in_file(&entry.input()));
+                failed += 1;
+            }
+        }
+    }
+
+    println!("\n{passed} passed, {failed} failed, {ignored} ignored");
+
+    if failed == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn read_baseline(path: &str) -> Result<Baseline, String> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|err| format!("cannot read baseline {path}: {err}"))?;
+
+    Baseline::parse(&text).map_err(|err| err.in_file(path).to_string())
+}
+
+// a baseline not saved yet starts out empty, but one that cannot be read or
+// parsed is an error rather than something to overwrite
+fn saved_baseline(path: &str) -> Result<Baseline, String> {
+    match std::fs::metadata(path) {
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            Ok(Baseline::default())
+        }
+        _ => read_baseline(path),
+    }
+}
+
+fn bench(bench: Bench, profile: &Profile) -> ExitCode {
+    let mut measurements = Vec::new();
+
+    for puzzle in registry::puzzles() {
+        if !bench.filter.matches(puzzle.year, puzzle.day) {
+            continue;
+        }
+
+        let path = profile.input(puzzle.year, puzzle.day);
+        let Ok(input) = std::fs::read_to_string(&path) else {
+            eprintln!("skipping {path}: cannot read input");
+            continue;
+        };
+
+        match bench::measure(&puzzle, &input, bench.iterations) {
+            Ok(measurement) => measurements.push(measurement),
+            Err(err) => {
+                eprintln!("{}", err.in_file(&path));
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    if bench.json {
+        print!("{}", bench::json(&measurements));
+    } else {
+        print!("{}", bench::table(&measurements));
+    }
+
+    let mut regressions = Vec::new();
+
+    if let Some(path) = &bench.baseline {
+        match read_baseline(path) {
+            Ok(baseline) => {
+                regressions = baseline.compare(&measurements, bench.threshold);
+            }
+            Err(err) => {
+                eprintln!("{err}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    for r in &regressions {
+        eprintln!(
+            "{} day {:02} {} is slower: {:.1?} -> {:.1?}",
+            r.year, r.day, r.phase, r.baseline, r.current
+        );
+    }
+
+    if let Some(path) = &bench.save {
+        let mut baseline = match saved_baseline(path) {
+            Ok(baseline) => baseline,
+            Err(err) => {
+                eprintln!("{err}");
+                return ExitCode::FAILURE;
+            }
+        };
+        baseline.merge(Baseline::from_measurements(&measurements));
+
+        if let Err(err) = std::fs::write(path, baseline.to_toml()) {
+            eprintln!("cannot write baseline {path}: {err}");
+            return ExitCode::FAILURE;
+        }
+    }
+
+    if regressions.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn new((year, day): (u16, u8)) -> ExitCode {
+    match scaffold::create(Path::new(""), year, day) {
+        Ok(changed) => {
+            for path in changed {
+                println!("{}", path.display());
+            }
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("cannot create {year} day {day}: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn extract_examples(
+    (year, day, page): (u16, u8, String),
+    profile: &Profile,
+) -> ExitCode {
+    let html = match std::fs::read_to_string(&page) {
+        Ok(html) => html,
+        Err(err) => {
+            eprintln!("cannot read {page}: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let manifest = match load_manifest(profile) {
+        Ok(manifest) => manifest,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let found = examples::extract(&html);
+    let dir = format!("{}/y{year}", profile.dir());
+
+    match examples::write(Path::new(&dir), day, &found) {
+        Ok(written) => {
+            for path in written {
+                println!("{}", path.display());
+            }
+        }
+        Err(err) => {
+            eprintln!("cannot write examples: {err}");
+            return ExitCode::FAILURE;
+        }
+    }
+
+    let entries = examples::manifest_entries(&manifest, year, day, &found);
+
+    if !entries.is_empty() {
+        // a profile without answers yet starts its manifest here
+        let path = profile.manifest();
+        let appended = match std::fs::read_to_string(&path) {
+            Ok(text) => Ok(format!("{text}\n{entries}")),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                Ok(entries.clone())
+            }
+            Err(err) => Err(err),
+        }
+        .and_then(|text| std::fs::write(&path, text));
+
+        if let Err(err) = appended {
+            eprintln!("cannot update {path}: {err}");
+            return ExitCode::FAILURE;
+        }
+
+        print!("\n{entries}");
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn client(profile: &Profile) -> Client {
+    Client::from_env().with_data_dir(profile.dir())
+}
+
+fn fetch((year, day): (u16, u8), profile: &Profile) -> ExitCode {
+    let client = client(profile);
+
+    match client.input(year, day) {
+        Ok(_) => {
+            println!("{}", client.input_path(year, day).display());
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("cannot fetch {year} day {day}: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn submit(submit: Submit, profile: &Profile) -> ExitCode {
+    let client = client(profile);
+
+    let answer = match submit.answer {
+        Some(answer) => Answer::from(answer),
+        None => {
+            let Some(puzzle) = registry::find(submit.year, submit.day) else {
+                eprintln!(
+                    "no solution for {} day {}",
+                    submit.year, submit.day
+                );
+                return ExitCode::FAILURE;
+            };
+
+            let parsed = client
+                .input(submit.year, submit.day)
+                .map_err(|err| err.to_string())
+                .and_then(|input| {
+                    puzzle.parse(&input).map_err(|err| err.to_string())
+                });
+
+            match parsed.map(|parsed| parsed.part(submit.part)) {
+                Ok(Some(answer)) if answer.is_solved() => answer,
+                Ok(_) => {
+                    eprintln!("no answer for part {}", submit.part);
+                    return ExitCode::FAILURE;
+                }
+                Err(err) => {
+                    eprintln!("{err}");
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+    };
+
+    match client.submit(submit.year, submit.day, submit.part, &answer) {
+        Ok(verdict) => {
+            println!("{answer}: {verdict}");
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("cannot submit {answer}: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let (profile, args) = match split_profile(std::env::args().skip(1)) {
+        Ok(split) => split,
+        Err(err) => {
+            eprintln!("{err}\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut args = args.into_iter().peekable();
+    let default = profile.clone().unwrap_or_default();
+
+    let command = match args.peek().map(String::as_str) {
+        Some("check") => {
+            parse_check(args.skip(1)).map(|args| check(args, profile))
+        }
+        Some("bench") => {
+            parse_bench(args.skip(1)).map(|args| bench(args, &default))
+        }
+        Some("new") => parse_day(args.skip(1)).map(new),
+        Some("examples") => parse_examples(args.skip(1))
+            .map(|args| extract_examples(args, &default)),
+        Some("fetch") => {
+            parse_day(args.skip(1)).map(|args| fetch(args, &default))
+        }
+        Some("submit") => {
+            parse_submit(args.skip(1)).map(|args| submit(args, &default))
+        }
+        _ => parse_args(args).map(|args| run(args, &default)),
+    };
+
+    command.unwrap_or_else(|err| {
+        eprintln!("{err}\n{USAGE}");
+        ExitCode::from(2)
+    })
+}
+
+fn run(args: Args, profile: &Profile) -> ExitCode {
+    let Some(puzzle) = registry::find(args.year, args.day) else {
+        eprintln!("no solution for {} day {}", args.year, args.day);
+        return ExitCode::FAILURE;
+    };
+
+    let default_input = profile.input(args.year, args.day);
+    let path = args.input.unwrap_or_else(|| default_input.clone());
+
+    let input = if path == default_input {
+        client(profile)
+            .input(args.year, args.day)
+            .map_err(|err| err.to_string())
+    } else {
+        read_input(&path).map_err(|err| err.to_string())
+    };
+
+    let input = match input {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("cannot read input {path}: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+
+    let manifest = match load_manifest(profile) {
+        Ok(manifest) => manifest,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let params = manifest.params(args.year, args.day, &path);
+
+    let parsed = match puzzle.parse_with(&input, &params) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            let file = if path == "-" { "<stdin>" } else { &path };
+            eprintln!("{}", err.in_file(file));
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut regressions = 0;
+
+    for part in parts {
+        let Some(answer) = parsed.part(part) else {
+            eprintln!("no part {part} for {} day {}", args.year, args.day);
+            return ExitCode::FAILURE;
+        };
+
+        match &answer {
+            Answer::Render(lines) => println!("Part {part}:\n{lines}"),
+            answer => println!("Part {part}: {answer}"),
+        }
+
+        let expected = manifest.find(args.year, args.day, &path, part);
+
+        if let Some(entry) =
+            expected.filter(|e| e.answer.is_solved() && e.answer != answer)
+        {
+            eprintln!("part {part} regressed, expected: {}", entry.answer);
+            regressions += 1;
+        }
+    }
+
+    if regressions == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
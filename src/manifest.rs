@@ -0,0 +1,335 @@
+use crate::error::ParseError;
+use crate::io::read_file;
+use crate::profile::Profile;
+use crate::registry;
+use crate::solution::{Answer, Params};
+use std::collections::HashMap;
+use std::path::Path;
+use toml::{Table, Value};
+
+pub const PATH: &str = "data/answers.toml";
+
+// the key next to the parts of an input holding its params
+pub const PARAMS: &str = "params";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub year: u16,
+    pub day: u8,
+    pub file: String,
+    pub part: usize,
+    pub answer: Answer,
+    pub ignore: Option<String>,
+    pub params: Params,
+    pub profile: Profile,
+}
+
+impl Entry {
+    pub fn input(&self) -> String {
+        format!("{}/y{}/{}", self.profile.dir(), self.year, self.file)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Manifest {
+    entries: Vec<Entry>,
+    // by year, day and file, also for inputs listing no parts
+    params: HashMap<(u16, u8, String), Params>,
+}
+
+impl Manifest {
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let table =
+            text.parse::<Table>().map_err(|err| syntax_error(text, &err))?;
+
+        let mut entries = Vec::new();
+        let mut inputs = HashMap::new();
+
+        for (key, days) in &table {
+            let year = key
+                .parse()
+                .map_err(|_| error_at(text, key, "a year as table key"))?;
+
+            for (key, files) in as_table(text, key, days)? {
+                let day = key
+                    .parse()
+                    .map_err(|_| error_at(text, key, "a day as table key"))?;
+
+                for (file, parts) in as_table(text, key, files)? {
+                    let parts = as_table(text, file, parts)?;
+                    let params = match parts.get(PARAMS) {
+                        Some(params) => parse_params(text, params)?,
+                        None => Params::default(),
+                    };
+                    inputs.insert((year, day, file.clone()), params.clone());
+
+                    for (part, value) in
+                        parts.iter().filter(|(key, _)| *key != PARAMS)
+                    {
+                        let (answer, ignore) = parse_value(text, part, value)?;
+                        let part = part
+                            .strip_prefix("part")
+                            .and_then(|n| n.parse().ok())
+                            .filter(|n| matches!(n, 1 | 2))
+                            .ok_or_else(|| {
+                                error_at(text, part, "part1 or part2")
+                            })?;
+
+                        entries.push(Entry {
+                            year,
+                            day,
+                            file: file.clone(),
+                            part,
+                            answer,
+                            ignore,
+                            params: params.clone(),
+                            profile: Profile::default(),
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(Self { entries, params: inputs })
+    }
+
+    pub fn for_profile(mut self, profile: &Profile) -> Self {
+        for entry in &mut self.entries {
+            entry.profile = profile.clone();
+        }
+
+        self
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    // the params of an input, none unless the manifest lists some
+    pub fn params(&self, year: u16, day: u8, input: &str) -> Params {
+        file_name(input)
+            .and_then(|file| self.params.get(&(year, day, file.to_string())))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn find(
+        &self,
+        year: u16,
+        day: u8,
+        input: &str,
+        part: usize,
+    ) -> Option<&Entry> {
+        let file = file_name(input)?;
+
+        self.entries.iter().find(|e| {
+            e.year == year && e.day == day && e.part == part && e.file == file
+        })
+    }
+}
+
+// inputs are told apart by year, day and file name, so the same input
+// matches however its path is spelled, like ./data/y2022/day01.txt
+fn file_name(input: &str) -> Option<&str> {
+    Path::new(input).file_name().and_then(|name| name.to_str())
+}
+
+fn as_table<'a>(
+    text: &str,
+    key: &str,
+    value: &'a Value,
+) -> Result<&'a Table, ParseError> {
+    value.as_table().ok_or_else(|| error_at(text, key, "a table"))
+}
+
+fn parse_params(text: &str, value: &Value) -> Result<Params, ParseError> {
+    let mut params = Params::default();
+
+    for (name, value) in as_table(text, PARAMS, value)? {
+        let value = value
+            .as_integer()
+            .ok_or_else(|| error_at(text, name, "a number"))?;
+        params.insert(name.as_str(), value);
+    }
+
+    Ok(params)
+}
+
+fn parse_value(
+    text: &str,
+    key: &str,
+    value: &Value,
+) -> Result<(Answer, Option<String>), ParseError> {
+    match value {
+        Value::Integer(n) => Ok((Answer::Integer(*n), None)),
+        Value::String(s) if s.contains('\n') => {
+            Ok((Answer::render(s.trim_end_matches('\n')), None))
+        }
+        Value::String(s) => Ok((Answer::from(s.as_str()), None)),
+        Value::Table(table) => {
+            let (answer, _) = match table.get("answer") {
+                Some(answer) => parse_value(text, key, answer)?,
+                None => (Answer::Unsolved, None),
+            };
+            let ignore = match table.get("ignore") {
+                Some(Value::String(reason)) => Some(reason.clone()),
+                Some(_) => {
+                    return Err(error_at(text, key, "an ignore reason"))
+                }
+                None => None,
+            };
+
+            Ok((answer, ignore))
+        }
+        _ => Err(error_at(text, key, "a number or a string")),
+    }
+}
+
+pub(crate) fn syntax_error(text: &str, err: &toml::de::Error) -> ParseError {
+    let expected = err.message().to_string();
+    let offset = err.span().map_or(0, |span| span.start);
+    let before = &text[..offset];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rsplit_once('\n')
+        .map_or(before, |(_, rest)| rest)
+        .chars()
+        .count()
+        + 1;
+
+    ParseError::new(expected).at(line, column)
+}
+
+// toml tables do not keep the spans of their keys, so errors point at the
+// first line mentioning the offending key
+fn error_at(text: &str, key: &str, expected: &str) -> ParseError {
+    let err = ParseError::new(expected);
+
+    text.lines()
+        .enumerate()
+        .find_map(|(row, line)| {
+            line.find(key).map(|col| {
+                err.clone().at(row + 1, line[..col].chars().count() + 1)
+            })
+        })
+        .unwrap_or(err)
+}
+
+pub fn load(profile: &Profile) -> Manifest {
+    let path = profile.manifest();
+
+    Manifest::parse(&read_file(&path))
+        .unwrap_or_else(|err| panic!("{}", err.in_file(&path)))
+        .for_profile(profile)
+}
+
+pub fn solve(entry: &Entry) -> Result<Answer, ParseError> {
+    let puzzle = registry::find(entry.year, entry.day)
+        .unwrap_or_else(|| panic!("no solution for {}", entry.input()));
+    let parsed =
+        puzzle.parse_with(&read_file(&entry.input()), &entry.params)?;
+
+    Ok(parsed.part(entry.part).unwrap_or(Answer::Unsolved))
+}
+
+// called by the tests generated from the manifest in build.rs
+pub fn verify(profile: &str, year: u16, day: u8, file: &str, part: usize) {
+    let profile = Profile::named(profile);
+    let manifest = load(&profile);
+    let input = format!("{}/y{year}/{file}", profile.dir());
+    let entry = manifest.find(year, day, &input, part).unwrap_or_else(|| {
+        panic!("{input} part {part} is not in {}", profile.manifest())
+    });
+
+    match solve(entry) {
+        Ok(answer) => assert_eq!(answer, entry.answer, "{input} part {part}"),
+        Err(err) => panic!("{}", err.in_file(&input)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_entries() {
+        let manifest = Manifest::parse(
+            "[2022.01]\n\
+             \"day01.txt\" = { part1 = 24_000, part2 = \"CMZ\" }\n\
+             \n\
+             [2022.10.\"day10.txt\"]\n\
+             part2 = { answer = '''\n#.\n.#\n''', ignore = \"slow\" }\n",
+        )
+        .unwrap();
+
+        assert_eq!(manifest.entries().len(), 3);
+        assert_eq!(
+            manifest.find(2022, 1, "data/y2022/day01.txt", 1).unwrap().answer,
+            Answer::Integer(24_000)
+        );
+        assert_eq!(
+            manifest.find(2022, 1, "data/y2022/day01.txt", 2).unwrap().answer,
+            Answer::from("CMZ")
+        );
+
+        let render = manifest.find(2022, 10, "data/y2022/day10.txt", 2);
+        assert_eq!(render.unwrap().answer, Answer::render("#.\n.#"));
+        assert_eq!(render.unwrap().ignore.as_deref(), Some("slow"));
+
+        let manifest = manifest.for_profile(&Profile::named("alice"));
+        assert_eq!(
+            manifest.entries()[0].input(),
+            "data/alice/y2022/day01.txt"
+        );
+    }
+
+    #[test]
+    fn parse_params() {
+        let manifest = Manifest::parse(
+            "[2022.15]\n\
+             \"day15-example.txt\" = { part1 = 26, params = { row = 10 } }\n\
+             \"day15.txt\" = { part1 = 4_879_972 }\n",
+        )
+        .unwrap();
+
+        assert_eq!(manifest.entries().len(), 2);
+
+        let example = "data/y2022/day15-example.txt";
+        assert_eq!(manifest.params(2022, 15, example).get("row"), Some(10));
+        assert_eq!(
+            manifest.params(2022, 15, "data/y2022/day15.txt"),
+            Params::default()
+        );
+
+        let spelled = "./data/y2022/../y2022/day15-example.txt";
+        assert_eq!(manifest.params(2022, 15, spelled).get("row"), Some(10));
+        assert!(manifest.find(2022, 15, "data/y2022/day15.txt", 1).is_some());
+        assert!(manifest.find(2022, 15, "day15.txt", 1).is_some());
+
+        let only = Manifest::parse(
+            "[2022.15]\n\"day15-example.txt\" = { params = { row = 10 } }\n",
+        )
+        .unwrap();
+        assert!(only.entries().is_empty());
+        assert_eq!(only.params(2022, 15, example).get("row"), Some(10));
+
+        let err = Manifest::parse(
+            "[2022.15]\n\"day15.txt\" = { params = { row = \"ten\" } }\n",
+        )
+        .unwrap_err();
+        assert_eq!((err.line, err.expected.as_str()), (2, "a number"));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err =
+            Manifest::parse("[2022.01]\n\"day01.txt\" = { part3 = 1 }\n")
+                .unwrap_err();
+        assert_eq!((err.line, err.column), (2, 17));
+        assert_eq!(err.expected, "part1 or part2");
+
+        let err = Manifest::parse("[2022.01]\n\"day01.txt\" = { part1 = }\n")
+            .unwrap_err();
+        assert_eq!(err.line, 2);
+    }
+}
@@ -0,0 +1,193 @@
+use crate::grid::Grid;
+
+// letters side by side the way puzzles draw them, 6 pixels high and most
+// of them 4 wide
+const SMALL: [(&str, &str); 2] = [
+    (
+        "ABCEFGHIJ",
+        "
+.##..###...##..####.####..##..#..#.###...##
+#..#.#..#.#..#.#....#....#..#.#..#..#.....#
+#..#.###..#....###..###..#....####..#.....#
+####.#..#.#....#....#....#.##.#..#..#.....#
+#..#.#..#.#..#.#....#....#..#.#..#..#..#..#
+#..#.###...##..####.#.....###.#..#.###..##.
+",
+    ),
+    (
+        "KLOPRSUYZ",
+        "
+#..#.#.....##..###..###...###.#..#.#...#.####
+#.#..#....#..#.#..#.#..#.#....#..#.#...#....#
+##...#....#..#.#..#.#..#.#....#..#..#.#....#.
+#.#..#....#..#.###..###...##..#..#...#....#..
+#.#..#....#..#.#....#.#.....#.#..#...#...#...
+#..#.####..##..#....#..#.###...##....#...####
+",
+    ),
+];
+
+// and the larger ones, 10 pixels high and 6 wide
+const LARGE: [(&str, &str); 3] = [
+    (
+        "ABCEF",
+        "
+..##....#####....####...######..######
+.#..#...#....#..#....#..#.......#.....
+#....#..#....#..#.......#.......#.....
+#....#..#....#..#.......#.......#.....
+#....#..#####...#.......#####...#####.
+######..#....#..#.......#.......#.....
+#....#..#....#..#.......#.......#.....
+#....#..#....#..#.......#.......#.....
+#....#..#....#..#....#..#.......#.....
+#....#..#####....####...######..#.....
+",
+    ),
+    (
+        "GHJKL",
+        "
+.####...#....#.....###..#....#..#.....
+#....#..#....#......#...#...#...#.....
+#.......#....#......#...#..#....#.....
+#.......#....#......#...#.#.....#.....
+#.......######......#...##......#.....
+#..###..#....#......#...##......#.....
+#....#..#....#......#...#.#.....#.....
+#....#..#....#..#...#...#..#....#.....
+#...##..#....#..#...#...#...#...#.....
+.###.#..#....#...###....#....#..######
+",
+    ),
+    (
+        "NPRXZ",
+        "
+#....#..#####...#####...#....#..######
+##...#..#....#..#....#..#....#.......#
+##...#..#....#..#....#...#..#........#
+#.#..#..#....#..#....#...#..#.......#.
+#.#..#..#####...#####.....##.......#..
+#..#.#..#.......#..#......##......#...
+#..#.#..#.......#...#....#..#....#....
+#...##..#.......#...#....#..#...#.....
+#...##..#.......#....#..#....#..#.....
+#....#..#.......#....#..#....#..######
+",
+    ),
+];
+
+// the letters in a grid of lit cells, None unless every glyph is a known
+// letter
+pub fn read(grid: &Grid<bool>) -> Option<String> {
+    decode(grid.rows().map(<[bool]>::to_vec).collect())
+}
+
+// like `read` for text where '#' is lit and anything else is dark
+pub fn read_str(text: &str) -> Option<String> {
+    decode(lit(text))
+}
+
+fn lit(text: &str) -> Vec<Vec<bool>> {
+    text.lines().map(|line| line.chars().map(|c| c == '#').collect()).collect()
+}
+
+fn decode(rows: Vec<Vec<bool>>) -> Option<String> {
+    let glyphs = glyphs(rows);
+    if glyphs.is_empty() {
+        return None;
+    }
+
+    glyphs.iter().map(|glyph| letter(glyph)).collect()
+}
+
+// the runs of columns with something lit in them, as rows of '#' and '.',
+// leaving out blank rows above and below the whole picture
+fn glyphs(mut rows: Vec<Vec<bool>>) -> Vec<Vec<String>> {
+    let blank = |row: &Vec<bool>| !row.contains(&true);
+    while rows.last().is_some_and(blank) {
+        rows.pop();
+    }
+    let top = rows.iter().position(|row| !blank(row)).unwrap_or(0);
+    let rows = &rows[top..];
+
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let lit = |x: usize, y: usize| rows[y].get(x) == Some(&true);
+    let blank_column = |x: usize| (0..rows.len()).all(|y| !lit(x, y));
+
+    let mut glyphs = vec![];
+    let mut x = 0;
+    while x < width {
+        if blank_column(x) {
+            x += 1;
+            continue;
+        }
+
+        let start = x;
+        while x < width && !blank_column(x) {
+            x += 1;
+        }
+
+        glyphs.push(
+            (0..rows.len())
+                .map(|y| {
+                    (start..x)
+                        .map(|x| if lit(x, y) { '#' } else { '.' })
+                        .collect()
+                })
+                .collect(),
+        );
+    }
+
+    glyphs
+}
+
+fn letter(glyph: &[String]) -> Option<char> {
+    let font = match glyph.len() {
+        6 => &SMALL[..],
+        10 => &LARGE[..],
+        _ => return None,
+    };
+
+    font.iter().find_map(|(letters, art)| {
+        letters
+            .chars()
+            .zip(glyphs(lit(art)))
+            .find_map(|(letter, known)| (known == glyph).then_some(letter))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fonts() {
+        for (letters, art) in SMALL.iter().chain(&LARGE) {
+            assert_eq!(read_str(art).as_deref(), Some(*letters));
+        }
+    }
+
+    #[test]
+    fn padded_words() {
+        let text = "
+..........
+.#..#..###
+.#..#...#.
+.####...#.
+.#..#...#.
+.#..#...#.
+.#..#..###
+..........
+";
+        assert_eq!(read_str(text), Some("HI".to_string()));
+
+        let grid = Grid::from_rows(lit(text.trim())).unwrap();
+        assert_eq!(read(&grid), Some("HI".to_string()));
+    }
+
+    #[test]
+    fn unknown_glyphs() {
+        assert_eq!(read_str("##..\n##..\n"), None);
+        assert_eq!(read_str("....\n"), None);
+    }
+}
@@ -0,0 +1,277 @@
+use crate::error::ParseError;
+use std::fmt;
+use std::hash::Hash;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+pub trait Scalar:
+    Copy
+    + Ord
+    + Hash
+    + fmt::Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Neg<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn abs(self) -> Self;
+}
+
+macro_rules! scalar {
+    ($($t:ty),*) => {
+        $(
+            impl Scalar for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn abs(self) -> Self {
+                    <$t>::abs(self)
+                }
+            }
+        )*
+    };
+}
+
+scalar!(i32, i64);
+
+// what flood fills need from a point, whatever its dimension
+pub trait Point: Copy + Eq + Hash {
+    fn orthogonal(&self) -> Vec<Self>;
+
+    // the smallest and largest coordinates of both points on each axis
+    fn lower(self, other: Self) -> Self;
+    fn upper(self, other: Self) -> Self;
+
+    // moves every coordinate by `by`, negative moving towards the origin
+    fn shift(self, by: i32) -> Self;
+
+    fn within(&self, min: &Self, max: &Self) -> bool;
+}
+
+// unit steps along each axis and in between, one entry per coordinate
+fn offsets<T: Scalar, const N: usize>(diagonals: bool) -> Vec<[T; N]> {
+    let steps = [-T::ONE, T::ZERO, T::ONE];
+    let mut offsets = vec![[T::ZERO; N]];
+
+    for axis in 0..N {
+        offsets = offsets
+            .into_iter()
+            .flat_map(|offset| {
+                steps.into_iter().map(move |step| {
+                    let mut next = offset;
+                    next[axis] = step;
+                    next
+                })
+            })
+            .collect();
+    }
+
+    offsets
+        .into_iter()
+        .filter(|offset| {
+            let moved = offset.iter().filter(|&&c| c != T::ZERO).count();
+
+            moved > 0 && (diagonals || moved == 1)
+        })
+        .collect()
+}
+
+macro_rules! point {
+    ($name:ident, $n:literal, $($field:ident),+) => {
+        #[derive(Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub struct $name<T> {
+            $(pub $field: T),+
+        }
+
+        impl<T: Scalar> $name<T> {
+            pub const fn new($($field: T),+) -> Self {
+                Self { $($field),+ }
+            }
+
+            pub const fn to_array(self) -> [T; $n] {
+                [$(self.$field),+]
+            }
+
+            pub const fn from_array([$($field),+]: [T; $n]) -> Self {
+                Self { $($field),+ }
+            }
+
+            pub fn manhattan(&self, rhs: &Self) -> T {
+                T::ZERO $(+ (self.$field - rhs.$field).abs())+
+            }
+
+            pub fn chebyshev(&self, rhs: &Self) -> T {
+                [$((self.$field - rhs.$field).abs()),+]
+                    .into_iter()
+                    .fold(T::ZERO, Ord::max)
+            }
+
+            // the points sharing a face, 2 per dimension
+            pub fn orthogonal(&self) -> Vec<Self> {
+                offsets::<T, $n>(false)
+                    .into_iter()
+                    .map(|offset| *self + Self::from_array(offset))
+                    .collect()
+            }
+
+            // the points sharing a face, edge or corner, 3^n - 1 of them
+            pub fn surrounding(&self) -> Vec<Self> {
+                offsets::<T, $n>(true)
+                    .into_iter()
+                    .map(|offset| *self + Self::from_array(offset))
+                    .collect()
+            }
+        }
+
+        impl<T: Scalar + From<i32>> Point for $name<T> {
+            fn orthogonal(&self) -> Vec<Self> {
+                Self::orthogonal(self)
+            }
+
+            fn lower(self, other: Self) -> Self {
+                Self { $($field: self.$field.min(other.$field)),+ }
+            }
+
+            fn upper(self, other: Self) -> Self {
+                Self { $($field: self.$field.max(other.$field)),+ }
+            }
+
+            fn shift(self, by: i32) -> Self {
+                Self { $($field: self.$field + T::from(by)),+ }
+            }
+
+            fn within(&self, min: &Self, max: &Self) -> bool {
+                $((min.$field..=max.$field).contains(&self.$field))&&+
+            }
+        }
+
+        impl<T: fmt::Debug> fmt::Debug for $name<T> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                let mut tuple = f.debug_tuple("");
+                $(tuple.field(&self.$field);)+
+                tuple.finish()
+            }
+        }
+
+        impl<T: Scalar> Add for $name<T> {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                Self { $($field: self.$field + other.$field),+ }
+            }
+        }
+
+        impl<T: Scalar> AddAssign for $name<T> {
+            fn add_assign(&mut self, other: Self) {
+                *self = *self + other;
+            }
+        }
+
+        impl<T: Scalar> Sub for $name<T> {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                Self { $($field: self.$field - other.$field),+ }
+            }
+        }
+
+        impl<T: Scalar> SubAssign for $name<T> {
+            fn sub_assign(&mut self, other: Self) {
+                *self = *self - other;
+            }
+        }
+
+        impl<T: Scalar> Neg for $name<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self { $($field: -self.$field),+ }
+            }
+        }
+
+        impl<T: Scalar> Mul<T> for $name<T> {
+            type Output = Self;
+
+            fn mul(self, factor: T) -> Self {
+                Self { $($field: self.$field * factor),+ }
+            }
+        }
+
+        // comma separated coordinates, like 1,2,3
+        impl<T> FromStr for $name<T>
+        where
+            T: Scalar + FromStr,
+            T::Err: Into<ParseError>,
+        {
+            type Err = ParseError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let coordinates = s.split(',').collect::<Vec<_>>();
+                let [$($field),+] = coordinates[..] else {
+                    return Err(ParseError::new(format!(
+                        "{} comma separated coordinates",
+                        $n
+                    )));
+                };
+
+                Ok(Self {
+                    $($field: crate::io::parse_token(s, $field.trim())?),+
+                })
+            }
+        }
+    };
+}
+
+point!(Point2, 2, x, y);
+point!(Point3, 3, x, y, z);
+point!(Point4, 4, x, y, z, w);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distances() {
+        let a = Point3::new(1i64, -2, 3);
+        let b = Point3::new(4, 2, 3);
+
+        assert_eq!(a.manhattan(&b), 7);
+        assert_eq!(a.chebyshev(&b), 4);
+        assert_eq!(b - a, Point3::new(3, 4, 0));
+        assert_eq!(-a * 2, Point3::new(-2, 4, -6));
+    }
+
+    #[test]
+    fn neighbour_counts() {
+        let origin = Point2::new(0, 0);
+        assert_eq!(origin.orthogonal().len(), 4);
+        assert_eq!(origin.surrounding().len(), 8);
+        assert!(origin.orthogonal().contains(&Point2::new(0, -1)));
+
+        assert_eq!(Point3::new(0, 0, 0).orthogonal().len(), 6);
+        assert_eq!(Point3::new(0, 0, 0).surrounding().len(), 26);
+        assert_eq!(Point4::new(0i64, 0, 0, 0).surrounding().len(), 80);
+    }
+
+    #[test]
+    fn searchable() {
+        let goal = Point3::new(2i64, -1, 3);
+        let steps = crate::search::shortest_path(
+            Point3::new(0, 0, 0),
+            |p| p == goal,
+            |p| p.orthogonal(),
+        );
+
+        assert_eq!(steps, Some(6));
+    }
+
+    #[test]
+    fn parse_coordinates() {
+        assert_eq!("1,-2,3".parse(), Ok(Point3::new(1, -2, 3)));
+        assert!("1,2".parse::<Point3<i32>>().is_err());
+        assert_eq!(format!("{:?}", Point2::new(1, 2)), "(1, 2)");
+    }
+}
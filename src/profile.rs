@@ -0,0 +1,87 @@
+use std::fs;
+use std::io;
+
+pub const DATA_DIR: &str = "data";
+
+// a set of puzzle inputs with its own answers, the default one being the
+// inputs directly under data/
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Profile(Option<String>);
+
+impl Profile {
+    pub fn named(name: &str) -> Self {
+        match name {
+            "" | "default" => Self(None),
+            name => Self(Some(name.to_string())),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        self.0.as_deref().unwrap_or("default")
+    }
+
+    pub fn dir(&self) -> String {
+        match &self.0 {
+            Some(name) => format!("{DATA_DIR}/{name}"),
+            None => DATA_DIR.to_string(),
+        }
+    }
+
+    pub fn input(&self, year: u16, day: u8) -> String {
+        format!("{}/y{year}/day{day:02}.txt", self.dir())
+    }
+
+    pub fn manifest(&self) -> String {
+        format!("{}/answers.toml", self.dir())
+    }
+}
+
+fn is_year(name: &str) -> bool {
+    name.strip_prefix('y')
+        .is_some_and(|year| year.chars().all(|c| c.is_ascii_digit()))
+}
+
+// the default profile followed by every directory under data/ that is not
+// a year
+pub fn profiles() -> io::Result<Vec<Profile>> {
+    let mut profiles = vec![];
+
+    for entry in fs::read_dir(DATA_DIR)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+
+        if entry.file_type()?.is_dir() && !is_year(&name) {
+            profiles.push(Profile::named(&name));
+        }
+    }
+
+    profiles.sort();
+    profiles.insert(0, Profile::default());
+
+    Ok(profiles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profile_paths() {
+        let default = Profile::named("default");
+        assert_eq!(default, Profile::default());
+        assert_eq!(default.input(2022, 16), "data/y2022/day16.txt");
+        assert_eq!(default.manifest(), "data/answers.toml");
+
+        let alice = Profile::named("alice");
+        assert_eq!(alice.name(), "alice");
+        assert_eq!(alice.input(2022, 1), "data/alice/y2022/day01.txt");
+        assert_eq!(alice.manifest(), "data/alice/answers.toml");
+    }
+
+    #[test]
+    fn year_directories_are_not_profiles() {
+        assert!(is_year("y2022"));
+        assert!(!is_year("alice"));
+        assert_eq!(profiles().unwrap()[0], Profile::default());
+    }
+}
@@ -0,0 +1,147 @@
+use crate::error::ParseError;
+use crate::solution::{Answer, Params, Solution};
+use crate::{y2019, y2022, y2023};
+
+pub trait Parsed {
+    fn part1(&self) -> Answer;
+
+    fn part2(&self) -> Answer;
+
+    fn part(&self, part: usize) -> Option<Answer> {
+        match part {
+            1 => Some(self.part1()),
+            2 => Some(self.part2()),
+            _ => None,
+        }
+    }
+}
+
+struct Input<S: Solution>(S::Input);
+
+impl<S: Solution> Parsed for Input<S> {
+    fn part1(&self) -> Answer {
+        S::part1(&self.0)
+    }
+
+    fn part2(&self) -> Answer {
+        S::part2(&self.0)
+    }
+}
+
+fn parse<S>(
+    input: &str,
+    params: &Params,
+) -> Result<Box<dyn Parsed>, ParseError>
+where
+    S: Solution + 'static,
+{
+    let input = S::parse_with(input, params)?;
+
+    Ok(Box::new(Input::<S>(input)))
+}
+
+type Parser = fn(&str, &Params) -> Result<Box<dyn Parsed>, ParseError>;
+
+#[derive(Clone, Copy)]
+pub struct Puzzle {
+    pub year: u16,
+    pub day: u8,
+    parse: Parser,
+}
+
+impl Puzzle {
+    pub fn new<S>(year: u16, day: u8) -> Self
+    where
+        S: Solution + 'static,
+    {
+        Self { year, day, parse: parse::<S> }
+    }
+
+    pub fn parse(&self, input: &str) -> Result<Box<dyn Parsed>, ParseError> {
+        self.parse_with(input, &Params::default())
+    }
+
+    pub fn parse_with(
+        &self,
+        input: &str,
+        params: &Params,
+    ) -> Result<Box<dyn Parsed>, ParseError> {
+        (self.parse)(input, params)
+    }
+
+    pub fn default_input(&self) -> String {
+        format!("data/y{}/day{:02}.txt", self.year, self.day)
+    }
+}
+
+pub fn puzzles() -> Vec<Puzzle> {
+    let mut puzzles = Vec::new();
+
+    puzzles.extend(y2019::puzzles());
+    puzzles.extend(y2022::puzzles());
+    puzzles.extend(y2023::puzzles());
+
+    puzzles
+}
+
+pub fn find(year: u16, day: u8) -> Option<Puzzle> {
+    puzzles().into_iter().find(|p| p.year == year && p.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::read_file;
+    use std::collections::HashSet;
+
+    #[test]
+    fn puzzles_are_unique() {
+        let puzzles = puzzles();
+        let keys =
+            puzzles.iter().map(|p| (p.year, p.day)).collect::<HashSet<_>>();
+
+        assert_eq!(keys.len(), puzzles.len());
+    }
+
+    #[test]
+    fn find_puzzle() {
+        let puzzle = find(2022, 1).expect("puzzle should be registered");
+        let parsed =
+            puzzle.parse(&read_file(&puzzle.default_input())).unwrap();
+
+        assert_eq!(puzzle.default_input(), "data/y2022/day01.txt");
+        assert_eq!(parsed.part(1), Some(Answer::Integer(69_177)));
+        assert_eq!(parsed.part(2), Some(Answer::Integer(207_456)));
+        assert!(parsed.part(0).is_none());
+        assert!(parsed.part(3).is_none());
+    }
+
+    #[test]
+    fn inputs_parse() {
+        for puzzle in puzzles() {
+            let input = read_file(&puzzle.default_input());
+
+            if let Err(err) = puzzle.parse(&input) {
+                panic!("{}", err.in_file(&puzzle.default_input()));
+            }
+        }
+    }
+
+    #[test]
+    fn parse_errors_are_reported() {
+        let puzzle = find(2022, 1).expect("puzzle should be registered");
+        let err = puzzle.parse("1000\n2000\n\n3000\nfour\n").err().unwrap();
+
+        assert_eq!((err.line, err.column), (5, 1));
+    }
+
+    #[test]
+    fn day25_has_a_single_part() {
+        let puzzle = find(2022, 25).expect("puzzle should be registered");
+        let parsed =
+            puzzle.parse(&read_file("data/y2022/day25-example.txt")).unwrap();
+
+        assert_eq!(parsed.part1(), Answer::from("2=-1=0"));
+        assert_eq!(parsed.part2(), Answer::Unsolved);
+    }
+}
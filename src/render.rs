@@ -0,0 +1,177 @@
+use crate::grid::{bounding_box, Grid, Position2D};
+use std::collections::HashMap;
+use std::fmt;
+
+// draws layers of glyphs over a background, later layers covering earlier
+// ones, within the bounding box of every layer unless given a viewport
+#[derive(Debug, Clone)]
+pub struct Render {
+    layers: Vec<HashMap<Position2D, char>>,
+    viewport: Option<(Position2D, Position2D)>,
+    background: char,
+    axes: bool,
+    y_up: bool,
+}
+
+impl Default for Render {
+    fn default() -> Self {
+        Self {
+            layers: vec![],
+            viewport: None,
+            background: '.',
+            axes: false,
+            y_up: false,
+        }
+    }
+}
+
+impl Render {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn grid<T>(grid: &Grid<T>, glyph: impl Fn(&T) -> char) -> Self {
+        Self::new().layer(grid.iter().map(|(p, value)| (p, glyph(value))))
+    }
+
+    pub fn map<T>(
+        map: &HashMap<Position2D, T>,
+        glyph: impl Fn(&T) -> char,
+    ) -> Self {
+        Self::new().layer(map.iter().map(|(&p, value)| (p, glyph(value))))
+    }
+
+    pub fn layer(
+        mut self,
+        cells: impl IntoIterator<Item = (Position2D, char)>,
+    ) -> Self {
+        self.layers.push(cells.into_iter().collect());
+        self
+    }
+
+    // draws the same glyph on every point, like a path over a map
+    pub fn overlay(
+        self,
+        points: impl IntoIterator<Item = Position2D>,
+        glyph: char,
+    ) -> Self {
+        self.layer(points.into_iter().map(|p| (p, glyph)))
+    }
+
+    // both corners included
+    pub const fn viewport(mut self, min: Position2D, max: Position2D) -> Self {
+        self.viewport = Some((min, max));
+        self
+    }
+
+    pub const fn background(mut self, glyph: char) -> Self {
+        self.background = glyph;
+        self
+    }
+
+    // y coordinates in front of each row and the last digit of the x
+    // coordinates above the columns
+    pub const fn axes(mut self) -> Self {
+        self.axes = true;
+        self
+    }
+
+    // draws the largest y at the top, for worlds where y grows upwards
+    pub const fn y_up(mut self) -> Self {
+        self.y_up = true;
+        self
+    }
+
+    fn glyph(&self, p: Position2D) -> char {
+        self.layers
+            .iter()
+            .rev()
+            .find_map(|layer| layer.get(&p))
+            .copied()
+            .unwrap_or(self.background)
+    }
+}
+
+impl fmt::Display for Render {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let bounds = self.viewport.or_else(|| {
+            bounding_box(self.layers.iter().flat_map(|l| l.keys().copied()))
+        });
+        let Some((min, max)) = bounds else {
+            return Ok(());
+        };
+
+        let margin = if self.axes {
+            min.y.to_string().len().max(max.y.to_string().len()) + 1
+        } else {
+            0
+        };
+
+        if self.axes {
+            write!(f, "{:margin$}", "")?;
+            for x in min.x..=max.x {
+                write!(f, "{}", x.rem_euclid(10))?;
+            }
+            writeln!(f)?;
+        }
+
+        let rows = min.y..=max.y;
+        let rows: Box<dyn Iterator<Item = i32>> =
+            if self.y_up { Box::new(rows.rev()) } else { Box::new(rows) };
+
+        for y in rows {
+            if self.axes {
+                write!(f, "{y:>width$} ", width = margin - 1)?;
+            }
+            for x in min.x..=max.x {
+                write!(f, "{}", self.glyph(Position2D::new(x, y)))?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn maze() -> Grid<bool> {
+        let rows = ["#####", "#...#", "#.#.#", "#####"]
+            .map(|row| row.chars().map(|c| c == '#').collect())
+            .to_vec();
+
+        Grid::from_rows(rows).unwrap()
+    }
+
+    #[test]
+    fn glyphs_and_overlays() {
+        let path = [(1, 2), (1, 1), (2, 1), (3, 1)]
+            .map(|(x, y)| Position2D::new(x, y));
+
+        let render =
+            Render::grid(&maze(), |&wall| if wall { '#' } else { ' ' })
+                .overlay(path, 'o')
+                .overlay([path[0]], 'S');
+
+        assert_eq!(render.to_string(), "#####\n#ooo#\n#S# #\n#####\n");
+    }
+
+    #[test]
+    fn viewport_and_axes() {
+        let map = HashMap::from([
+            (Position2D::new(-1, 9), 'a'),
+            (Position2D::new(1, 10), 'b'),
+        ]);
+
+        let render = Render::map(&map, |&c| c).axes();
+        assert_eq!(render.to_string(), "   901\n 9 a..\n10 ..b\n");
+
+        let render = Render::map(&map, |&c| c)
+            .viewport(Position2D::new(0, 9), Position2D::new(2, 10))
+            .background(' ')
+            .y_up();
+        assert_eq!(render.to_string(), " b \n   \n");
+    }
+}
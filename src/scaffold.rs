@@ -0,0 +1,424 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub fn module(day: u8) -> String {
+    format!(
+        "use crate::error::ParseError;
+use crate::io;
+use crate::solution::{{Answer, Solution}};
+
+fn parse_input(input: &str) -> Result<Vec<String>, ParseError> {{
+    io::parse_lines(input, |line| Ok(line.to_string()))
+}}
+
+pub struct Day{day:02};
+
+impl Solution for Day{day:02} {{
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {{
+        parse_input(input)
+    }}
+
+    fn part1(_input: &Self::Input) -> Answer {{
+        Answer::Unsolved
+    }}
+
+    fn part2(_input: &Self::Input) -> Answer {{
+        Answer::Unsolved
+    }}
+}}
+"
+    )
+}
+
+pub fn year_module(year: u16, day: u8) -> String {
+    format!(
+        "use crate::registry::Puzzle;
+
+mod day{day:02};
+
+pub fn puzzles() -> Vec<Puzzle> {{
+    vec![
+        Puzzle::new::<day{day:02}::Day{day:02}>({year}, {day}),
+    ]
+}}
+"
+    )
+}
+
+// inserts `line` before the first line starting with `prefix` that sorts
+// after it, or else after the last of them
+fn insert_sorted(text: &str, prefix: &str, line: &str) -> Option<String> {
+    let lines = text.lines().collect::<Vec<_>>();
+
+    if lines.contains(&line) {
+        return Some(text.to_string());
+    }
+
+    let matching = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| l.trim_start().starts_with(prefix))
+        .collect::<Vec<_>>();
+
+    let at = match matching.iter().find(|(_, l)| **l > line) {
+        Some((i, _)) => *i,
+        None => matching.last()?.0 + 1,
+    };
+
+    let mut lines = lines;
+    lines.insert(at, line);
+
+    Some(lines.join("\n") + "\n")
+}
+
+pub fn register_day(mod_rs: &str, year: u16, day: u8) -> Option<String> {
+    let text = insert_sorted(mod_rs, "mod day", &format!("mod day{day:02};"))?;
+
+    insert_sorted(
+        &text,
+        "Puzzle::new::<",
+        &format!(
+            "        Puzzle::new::<day{day:02}::Day{day:02}>({year}, {day}),"
+        ),
+    )
+}
+
+pub fn register_year(
+    lib_rs: &str,
+    registry_rs: &str,
+    year: u16,
+) -> Option<(String, String)> {
+    let lib_rs = insert_sorted(lib_rs, "mod y", &format!("mod y{year};"))?;
+
+    let (start, rest) = registry_rs.split_once("use crate::{y")?;
+    let (years, end) = rest.split_once("};")?;
+    let mut years = format!("y{years}")
+        .split(", ")
+        .map(str::to_string)
+        .chain([format!("y{year}")])
+        .collect::<Vec<_>>();
+    years.sort();
+    years.dedup();
+
+    let registry_rs =
+        format!("{start}use crate::{{{}}};{end}", years.join(", "));
+    let registry_rs = insert_sorted(
+        &registry_rs,
+        "puzzles.extend(",
+        &format!("    puzzles.extend(y{year}::puzzles());"),
+    )?;
+
+    Some((lib_rs, registry_rs))
+}
+
+pub fn manifest_entries(year: u16, day: u8) -> String {
+    [format!("day{day:02}-example.txt"), format!("day{day:02}.txt")]
+        .iter()
+        .map(|file| {
+            format!(
+                "[{year}.{day:02}.\"{file}\"]\n\
+                 part1 = {{ ignore = \"unsolved\" }}\n\
+                 part2 = {{ ignore = \"unsolved\" }}\n"
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// a file `create` writes, with what it held before, if anything, so that
+// a failed scaffold can be put back the way it was
+struct Change {
+    path: PathBuf,
+    contents: String,
+    original: Option<String>,
+}
+
+impl Change {
+    fn new(path: PathBuf, contents: String) -> Self {
+        Self { path, contents, original: None }
+    }
+
+    fn edit(
+        path: PathBuf,
+        f: impl FnOnce(&str) -> Option<String>,
+    ) -> io::Result<Self> {
+        let original = fs::read_to_string(&path)?;
+        let contents = f(&original).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("cannot register module in {}", path.display()),
+            )
+        })?;
+
+        Ok(Self { path, contents, original: Some(original) })
+    }
+
+    // new files must not exist yet and edited ones must be writable
+    fn check(&self) -> io::Result<()> {
+        let display = self.path.display();
+
+        match &self.original {
+            None if self.path.exists() => Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{display} already exists"),
+            )),
+            Some(_) if fs::metadata(&self.path)?.permissions().readonly() => {
+                Err(io::Error::new(
+                    io::ErrorKind::PermissionDenied,
+                    format!("{display} is read-only"),
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn write(&self) -> io::Result<()> {
+        if self.original.is_some() {
+            return fs::write(&self.path, &self.contents);
+        }
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&self.path)
+            .and_then(|mut file| {
+                io::Write::write_all(&mut file, self.contents.as_bytes())
+            })
+    }
+
+    // best effort, as this only runs once something already failed
+    fn undo(&self) {
+        let _ = match &self.original {
+            Some(original) => fs::write(&self.path, original),
+            None => fs::remove_file(&self.path),
+        };
+    }
+}
+
+// checks every change before writing any, and if a write still fails puts
+// back the ones written so far
+fn apply(changes: &[Change]) -> io::Result<()> {
+    for change in changes {
+        change.check()?;
+    }
+
+    for (i, change) in changes.iter().enumerate() {
+        if let Err(err) = change.write() {
+            changes[..=i].iter().rev().for_each(Change::undo);
+            return Err(err);
+        }
+    }
+
+    Ok(())
+}
+
+// creates the module and data placeholders for a day under `root`,
+// returning every file created or changed. nothing is written unless every
+// change can be made
+pub fn create(root: &Path, year: u16, day: u8) -> io::Result<Vec<PathBuf>> {
+    let src = root.join("src");
+    let data = root.join(format!("data/y{year}"));
+    let module_rs = src.join(format!("y{year}/day{day:02}.rs"));
+    let mod_rs = src.join(format!("y{year}/mod.rs"));
+    let manifest = root.join(crate::manifest::PATH);
+
+    let mut changes = vec![];
+
+    if mod_rs.exists() {
+        changes.push(Change::new(module_rs, module(day)));
+        changes
+            .push(Change::edit(mod_rs, |text| register_day(text, year, day))?);
+    } else {
+        let lib_rs = src.join("lib.rs");
+        let registry_rs = src.join("registry.rs");
+        let lib = fs::read_to_string(&lib_rs)?;
+        let registry = fs::read_to_string(&registry_rs)?;
+
+        let (new_lib, new_registry) = register_year(&lib, &registry, year)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "cannot register year",
+                )
+            })?;
+
+        changes.push(Change {
+            path: lib_rs,
+            contents: new_lib,
+            original: Some(lib),
+        });
+        changes.push(Change {
+            path: registry_rs,
+            contents: new_registry,
+            original: Some(registry),
+        });
+        changes.push(Change::new(module_rs, module(day)));
+        changes.push(Change::new(mod_rs, year_module(year, day)));
+    }
+
+    for file in
+        [format!("day{day:02}-example.txt"), format!("day{day:02}.txt")]
+    {
+        let path = data.join(file);
+
+        if !path.exists() {
+            changes.push(Change::new(path, String::new()));
+        }
+    }
+
+    let entries = manifest_entries(year, day);
+
+    match fs::read_to_string(&manifest) {
+        Ok(text) if text.contains(&format!("[{year}.{day:02}")) => {}
+        Ok(text) => {
+            let separator = if text.is_empty() { "" } else { "\n" };
+            changes.push(Change {
+                path: manifest,
+                contents: format!("{text}{separator}{entries}"),
+                original: Some(text),
+            });
+        }
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            changes.push(Change::new(manifest, entries));
+        }
+        Err(err) => return Err(err),
+    }
+
+    apply(&changes)?;
+
+    Ok(changes.into_iter().map(|change| change.path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TempDir;
+
+    const MOD_RS: &str = "use crate::registry::Puzzle;
+
+mod day01;
+mod day03;
+
+pub fn puzzles() -> Vec<Puzzle> {
+    vec![
+        Puzzle::new::<day01::Day01>(2023, 1),
+        Puzzle::new::<day03::Day03>(2023, 3),
+    ]
+}
+";
+
+    #[test]
+    fn days_are_registered_in_order() {
+        let text = register_day(MOD_RS, 2023, 2).unwrap();
+
+        assert!(text.contains("mod day01;\nmod day02;\nmod day03;\n"));
+        assert!(text.contains(
+            "(2023, 1),\n        Puzzle::new::<day02::Day02>(2023, 2),\n"
+        ));
+        assert_eq!(register_day(&text, 2023, 2).unwrap(), text);
+
+        let text = register_day(&year_module(2024, 1), 2024, 5).unwrap();
+        assert!(text.contains("mod day01;\nmod day05;\n\npub fn"));
+        assert!(
+            text.contains("(2024, 1),\n        Puzzle::new::<day05::Day05>")
+        );
+    }
+
+    #[test]
+    fn years_are_registered() {
+        let lib_rs = "mod io;\nmod y2019;\nmod y2023;\n";
+        let registry_rs = "use crate::{y2019, y2023};
+
+pub fn puzzles() -> Vec<Puzzle> {
+    let mut puzzles = Vec::new();
+
+    puzzles.extend(y2019::puzzles());
+    puzzles.extend(y2023::puzzles());
+
+    puzzles
+}
+";
+
+        let (lib_rs, registry_rs) =
+            register_year(lib_rs, registry_rs, 2022).unwrap();
+
+        assert_eq!(lib_rs, "mod io;\nmod y2019;\nmod y2022;\nmod y2023;\n");
+        assert!(registry_rs.starts_with("use crate::{y2019, y2022, y2023};"));
+        assert!(registry_rs.contains(
+            "(y2019::puzzles());\n    puzzles.extend(y2022::puzzles());\n"
+        ));
+    }
+
+    #[test]
+    fn generated_manifest_entries_parse() {
+        let manifest =
+            crate::manifest::Manifest::parse(&manifest_entries(2023, 17))
+                .unwrap();
+
+        assert_eq!(manifest.entries().len(), 4);
+        assert!(manifest.entries().iter().all(|e| e.ignore.is_some()));
+    }
+
+    #[test]
+    fn create_day() {
+        let dir = TempDir::new("scaffold-create-day");
+        let root = dir.path();
+        fs::create_dir_all(root.join("src/y2023")).unwrap();
+        fs::create_dir_all(root.join("data")).unwrap();
+        fs::write(root.join("src/y2023/mod.rs"), MOD_RS).unwrap();
+
+        let changed = create(root, 2023, 17).unwrap();
+
+        assert_eq!(changed.len(), 5);
+        assert!(fs::read_to_string(root.join("src/y2023/day17.rs"))
+            .unwrap()
+            .contains("impl Solution for Day17"));
+        assert!(root.join("data/y2023/day17-example.txt").exists());
+        assert!(fs::read_to_string(root.join("data/answers.toml"))
+            .unwrap()
+            .starts_with("[2023.17.\"day17-example.txt\"]"));
+
+        let err = create(root, 2023, 17).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+    }
+
+    #[test]
+    fn failed_days_leave_no_trace() {
+        let dir = TempDir::new("scaffold-failed-days");
+        let root = dir.path();
+        fs::create_dir_all(root.join("src/y2023")).unwrap();
+        fs::create_dir_all(root.join("data/answers.toml")).unwrap();
+        fs::write(root.join("src/y2023/mod.rs"), MOD_RS).unwrap();
+
+        // the manifest cannot be read, and so nothing else is written
+        assert!(create(root, 2023, 17).is_err());
+        assert!(!root.join("src/y2023/day17.rs").exists());
+        assert!(!root.join("data/y2023").exists());
+        assert_eq!(
+            fs::read_to_string(root.join("src/y2023/mod.rs")).unwrap(),
+            MOD_RS
+        );
+
+        // nor when it cannot be written
+        fs::remove_dir(root.join("data/answers.toml")).unwrap();
+        fs::write(root.join("data/answers.toml"), "").unwrap();
+        let mut permissions = fs::metadata(root.join("data/answers.toml"))
+            .unwrap()
+            .permissions();
+        permissions.set_readonly(true);
+        fs::set_permissions(root.join("data/answers.toml"), permissions)
+            .unwrap();
+
+        let err = create(root, 2023, 17).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+        assert!(!root.join("src/y2023/day17.rs").exists());
+        assert!(!root.join("data/y2023").exists());
+    }
+}
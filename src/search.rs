@@ -1,29 +1,30 @@
 use std::collections::VecDeque;
 use std::{
-    collections::{BinaryHeap, HashSet},
+    collections::{BinaryHeap, HashMap, HashSet},
     hash::Hash,
+    ops::Add,
 };
 
-struct MinHeapContainer<T> {
-    cost: usize,
+struct MinHeapContainer<C, T> {
+    cost: C,
     value: T,
 }
 
-impl<T> PartialEq for MinHeapContainer<T> {
+impl<C: Ord, T> PartialEq for MinHeapContainer<C, T> {
     fn eq(&self, other: &Self) -> bool {
         self.cost == other.cost
     }
 }
 
-impl<T> PartialOrd for MinHeapContainer<T> {
+impl<C: Ord, T> PartialOrd for MinHeapContainer<C, T> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
 
-impl<T> Eq for MinHeapContainer<T> {}
+impl<C: Ord, T> Eq for MinHeapContainer<C, T> {}
 
-impl<T> Ord for MinHeapContainer<T> {
+impl<C: Ord, T> Ord for MinHeapContainer<C, T> {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         self.cost.cmp(&other.cost).reverse()
     }
@@ -64,6 +65,267 @@ where
     None
 }
 
+// what a search found: the best known cost of every node seen and every
+// node each was reached from at that cost, enough to rebuild one or all of
+// the cheapest paths to the goal. parents are always settled before their
+// children, so following them never goes round in circles, even along
+// steps that cost nothing
+pub struct Paths<T, C> {
+    start: T,
+    goal: Option<T>,
+    costs: HashMap<T, C>,
+    parents: HashMap<T, Vec<T>>,
+    settled: Vec<T>,
+    expanded: usize,
+}
+
+impl<T, C> Paths<T, C>
+where
+    T: Eq + Hash + Copy,
+    C: Copy + Ord,
+{
+    pub const fn goal(&self) -> Option<T> {
+        self.goal
+    }
+
+    pub fn cost(&self) -> Option<C> {
+        self.goal.map(|goal| self.costs[&goal])
+    }
+
+    pub const fn parents(&self) -> &HashMap<T, Vec<T>> {
+        &self.parents
+    }
+
+    // one of the cheapest paths to the goal, both ends included
+    pub fn path(&self) -> Option<Vec<T>> {
+        let mut path = vec![self.goal?];
+
+        while let Some(parents) = self.parents.get(&path[path.len() - 1]) {
+            path.push(parents[0]);
+        }
+
+        path.reverse();
+        Some(path)
+    }
+
+    // how many different cheapest paths lead to the goal, None if there
+    // are more than fit in a u64
+    pub fn count(&self) -> Option<u64> {
+        let Some(goal) = self.goal else {
+            return Some(0);
+        };
+
+        // in the order they were settled, so every parent is counted first
+        let mut counts = HashMap::from([(self.start, 1u64)]);
+        for &node in self.settled.iter().chain([&goal]) {
+            if let Some(parents) = self.parents.get(&node) {
+                let count = parents
+                    .iter()
+                    .try_fold(0u64, |sum, p| sum.checked_add(counts[p]))?;
+                counts.insert(node, count);
+            }
+        }
+
+        Some(counts[&goal])
+    }
+
+    // every cheapest path to the goal, of which there can be very many
+    pub fn all(&self) -> Vec<Vec<T>> {
+        let Some(goal) = self.goal else {
+            return vec![];
+        };
+
+        let mut paths = vec![];
+        let mut partial = vec![vec![goal]];
+
+        while let Some(path) = partial.pop() {
+            match self.parents.get(&path[path.len() - 1]) {
+                None => paths.push(path.into_iter().rev().collect()),
+                Some(parents) => {
+                    partial.extend(parents.iter().map(|&parent| {
+                        let mut path = path.clone();
+                        path.push(parent);
+                        path
+                    }));
+                }
+            }
+        }
+
+        paths
+    }
+}
+
+// the cheapest cost from start to a node reaching the goal, neighbours
+// coming with the cost of the step to them, keeping track of how each node
+// was reached on the way. the frontier is ordered by the cost so far plus
+// the heuristic, which must never overestimate the cost left. parents are
+// only kept when asked for, as most searches just want the cost, and only
+// from nodes already settled to ones that are not
+fn cheapest<T, C, G, N, I, H>(
+    start: T,
+    goal: G,
+    neighbours: N,
+    heuristic: H,
+    track: bool,
+) -> Paths<T, C>
+where
+    T: Eq + Hash + Copy,
+    C: Copy + Ord + Default + Add<Output = C>,
+    G: Fn(T) -> bool,
+    N: Fn(T) -> I,
+    I: IntoIterator<Item = (T, C)>,
+    H: Fn(T) -> C,
+{
+    let mut frontier = BinaryHeap::new();
+    let mut costs = HashMap::new();
+    let mut parents = HashMap::<T, Vec<T>>::new();
+    let mut settled = HashSet::new();
+    let mut order = vec![];
+    let mut expanded = 0;
+
+    costs.insert(start, C::default());
+    frontier.push(MinHeapContainer {
+        cost: heuristic(start),
+        value: (C::default(), start),
+    });
+
+    let mut found = None;
+
+    while let Some(MinHeapContainer { cost: estimate, value: (cost, value) }) =
+        frontier.pop()
+    {
+        // when tracking, nodes as cheap as the goal are still settled, as
+        // they may lead there for nothing
+        if found.is_some_and(|(_, best)| estimate > best) {
+            break;
+        }
+
+        // a cheaper way here was already taken
+        if costs.get(&value).is_some_and(|&best| best < cost) {
+            continue;
+        }
+
+        if goal(value) {
+            found = found.or(Some((value, cost)));
+            if !track {
+                break;
+            }
+            continue;
+        }
+
+        if track && !settled.insert(value) {
+            continue;
+        }
+
+        expanded += 1;
+        if track {
+            order.push(value);
+        }
+
+        for (el, step) in neighbours(value) {
+            let new_cost = cost + step;
+
+            match costs.get(&el) {
+                Some(&best) if best < new_cost => {}
+                Some(&best) if best == new_cost => {
+                    // the start and settled nodes already have all theirs
+                    if track && el != start && !settled.contains(&el) {
+                        parents.entry(el).or_default().push(value);
+                    }
+                }
+                _ => {
+                    costs.insert(el, new_cost);
+                    if track {
+                        parents.insert(el, vec![value]);
+                    }
+                    frontier.push(MinHeapContainer {
+                        cost: new_cost + heuristic(el),
+                        value: (new_cost, el),
+                    });
+                }
+            }
+        }
+    }
+
+    Paths {
+        start,
+        goal: found.map(|(goal, _)| goal),
+        costs,
+        parents,
+        settled: order,
+        expanded,
+    }
+}
+
+pub fn dijkstra<T, C, G, N, I>(start: T, goal: G, neighbours: N) -> Option<C>
+where
+    T: Eq + Hash + Copy,
+    C: Copy + Ord + Default + Add<Output = C>,
+    G: Fn(T) -> bool,
+    N: Fn(T) -> I,
+    I: IntoIterator<Item = (T, C)>,
+{
+    cheapest(start, goal, neighbours, |_| C::default(), false).cost()
+}
+
+// the cheapest cost to every node reachable from start
+pub fn distances<T, C, N, I>(start: T, neighbours: N) -> HashMap<T, C>
+where
+    T: Eq + Hash + Copy,
+    C: Copy + Ord + Default + Add<Output = C>,
+    N: Fn(T) -> I,
+    I: IntoIterator<Item = (T, C)>,
+{
+    cheapest(start, |_| false, neighbours, |_| C::default(), false).costs
+}
+
+// dijkstra guided by a heuristic, also counting the nodes expanded before
+// reaching the goal, which a zero heuristic turns into dijkstra's count
+pub fn astar<T, C, G, N, I, H>(
+    start: T,
+    goal: G,
+    neighbours: N,
+    heuristic: H,
+) -> (Option<C>, usize)
+where
+    T: Eq + Hash + Copy,
+    C: Copy + Ord + Default + Add<Output = C>,
+    G: Fn(T) -> bool,
+    N: Fn(T) -> I,
+    I: IntoIterator<Item = (T, C)>,
+    H: Fn(T) -> C,
+{
+    let paths = cheapest(start, goal, neighbours, heuristic, false);
+
+    (paths.cost(), paths.expanded)
+}
+
+// every cheapest path from start to the goal at once, see Paths
+pub fn paths<T, C, G, N, I>(start: T, goal: G, neighbours: N) -> Paths<T, C>
+where
+    T: Eq + Hash + Copy,
+    C: Copy + Ord + Default + Add<Output = C>,
+    G: Fn(T) -> bool,
+    N: Fn(T) -> I,
+    I: IntoIterator<Item = (T, C)>,
+{
+    cheapest(start, goal, neighbours, |_| C::default(), true)
+}
+
+// like shortest_path with the paths themselves, every step costing 1
+pub fn shortest_paths<T, G, N>(
+    start: T,
+    goal: G,
+    neighbours: N,
+) -> Paths<T, usize>
+where
+    T: Eq + Hash + Copy,
+    G: Fn(T) -> bool,
+    N: Fn(T) -> Vec<T>,
+{
+    paths(start, goal, |node| neighbours(node).into_iter().map(|n| (n, 1)))
+}
+
 pub fn bfs<T, N>(start: T, neighbours: N) -> Vec<T>
 where
     T: Eq + Hash + Copy,
@@ -87,3 +349,130 @@ where
 
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::point::Point2;
+
+    // a -1- b -1- c, with a direct a -5- c and a dead end d
+    fn edges(node: char) -> Vec<(char, u32)> {
+        match node {
+            'a' => vec![('b', 1), ('c', 5)],
+            'b' => vec![('a', 1), ('c', 1)],
+            'c' => vec![('a', 5), ('b', 1), ('d', 3)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn weighted_edges() {
+        assert_eq!(dijkstra('a', |n| n == 'c', edges), Some(2));
+        assert_eq!(dijkstra('d', |n| n == 'a', edges), None);
+
+        let costs = distances('a', edges);
+        assert_eq!(costs.len(), 4);
+        assert_eq!((costs[&'a'], costs[&'c'], costs[&'d']), (0, 2, 5));
+    }
+
+    #[test]
+    fn all_shortest_paths() {
+        // corner to corner of a 3 by 3 square, 2 steps right and 2 down
+        let square = |p: Point2<i32>| {
+            p.orthogonal()
+                .into_iter()
+                .filter(|p| (0..3).contains(&p.x) && (0..3).contains(&p.y))
+                .collect::<Vec<_>>()
+        };
+        let (start, goal) = (Point2::new(0, 0), Point2::new(2, 2));
+
+        let paths = shortest_paths(start, |p| p == goal, square);
+        assert_eq!(paths.cost(), Some(4));
+        assert_eq!(paths.count(), Some(6));
+        assert_eq!(paths.parents()[&goal].len(), 2);
+
+        let path = paths.path().unwrap();
+        assert_eq!((path.len(), path[0], path[4]), (5, start, goal));
+        assert!(path.windows(2).all(|w| w[0].manhattan(&w[1]) == 1));
+
+        let mut all = paths.all();
+        all.sort();
+        assert_eq!(all.len(), 6);
+        assert!(all.windows(2).all(|w| w[0] != w[1]));
+        assert!(all.contains(&path));
+
+        let nowhere = shortest_paths(start, |p| p.x > 5, square);
+        assert_eq!((nowhere.path(), nowhere.count()), (None, Some(0)));
+    }
+
+    #[test]
+    fn free_steps() {
+        // a and b go back and forth for nothing, either way on to c
+        let free = |node: char| match node {
+            'a' => vec![('b', 0), ('c', 1)],
+            'b' => vec![('a', 0), ('c', 1)],
+            _ => vec![],
+        };
+
+        let paths = paths('a', |n| n == 'c', free);
+        assert_eq!(paths.cost(), Some(1));
+        assert_eq!(paths.path(), Some(vec!['a', 'c']));
+        assert_eq!(paths.count(), Some(2));
+
+        let mut all = paths.all();
+        all.sort();
+        assert_eq!(all, [vec!['a', 'b', 'c'], vec!['a', 'c']]);
+    }
+
+    #[test]
+    fn free_steps_after_the_goal() {
+        // b costs as much as c, so may be settled after c, and still leads
+        // there for nothing
+        let late = |node: char| match node {
+            'a' => vec![('c', 1), ('b', 1)],
+            'b' => vec![('c', 0)],
+            _ => vec![],
+        };
+
+        let paths = paths('a', |n| n == 'c', late);
+        assert_eq!(paths.cost(), Some(1));
+        assert_eq!(paths.count(), Some(2));
+
+        let mut all = paths.all();
+        all.sort();
+        assert_eq!(all, [vec!['a', 'b', 'c'], vec!['a', 'c']]);
+    }
+
+    #[test]
+    fn too_many_paths_to_count() {
+        // two ways over each of 70 steps in a row
+        let ladder = |n: u32| vec![n + 2 - n % 2, n + 3 - n % 2];
+
+        let paths = shortest_paths(0, |n| n >= 140, ladder);
+        assert_eq!(paths.cost(), Some(70));
+        assert_eq!(paths.count(), None);
+    }
+
+    #[test]
+    fn heuristic_expands_fewer_nodes() {
+        let goal = Point2::new(20, 0);
+        let open = |p: Point2<i32>| {
+            p.orthogonal()
+                .into_iter()
+                .filter(|p| p.x.abs() <= 20 && p.y.abs() <= 20)
+                .map(|p| (p, 1))
+                .collect::<Vec<_>>()
+        };
+
+        let (cost, guided) = astar(
+            Point2::new(0, 0),
+            |p| p == goal,
+            open,
+            |p| p.manhattan(&goal),
+        );
+        let (_, blind) = astar(Point2::new(0, 0), |p| p == goal, open, |_| 0);
+
+        assert_eq!(cost, Some(20));
+        assert!(guided * 10 < blind, "{guided} against {blind}");
+    }
+}
@@ -0,0 +1,126 @@
+use crate::error::ParseError;
+use std::collections::BTreeMap;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Integer(i64),
+    Text(String),
+    Render(String),
+    Unsolved,
+}
+
+impl Answer {
+    pub fn render(lines: impl Into<String>) -> Self {
+        Self::Render(lines.into())
+    }
+
+    pub const fn is_solved(&self) -> bool {
+        !matches!(self, Self::Unsolved)
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Integer(v) => write!(f, "{v}"),
+            Self::Text(s) | Self::Render(s) => write!(f, "{s}"),
+            Self::Unsolved => write!(f, "unsolved"),
+        }
+    }
+}
+
+macro_rules! integer_answer {
+    ($($t:ty),+) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Self::Integer(
+                        i64::try_from(value).expect("answer should fit in i64"),
+                    )
+                }
+            }
+        )+
+    };
+}
+
+integer_answer!(i16, i32, i64, u16, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+// named numbers a puzzle states in its text rather than its input, like
+// the row 2022 day 15 asks about, which differ between the examples and the
+// real puzzle. they are kept with each input's answers in the manifest
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params(BTreeMap<String, i64>);
+
+impl Params {
+    pub fn get(&self, name: &str) -> Option<i64> {
+        self.0.get(name).copied()
+    }
+
+    pub fn insert(&mut self, name: impl Into<String>, value: i64) {
+        self.0.insert(name.into(), value);
+    }
+}
+
+pub trait Solution {
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    // days without params ignore them
+    fn parse_with(
+        input: &str,
+        _params: &Params,
+    ) -> Result<Self::Input, ParseError> {
+        Self::parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer;
+
+    fn part2(_input: &Self::Input) -> Answer {
+        Answer::Unsolved
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integer_answers() {
+        assert_eq!(Answer::from(42_usize), Answer::Integer(42));
+        assert_eq!(Answer::from(-7_i32), Answer::Integer(-7));
+        assert_eq!(
+            Answer::from(13_340_867_187_704_i64).to_string(),
+            "13340867187704"
+        );
+    }
+
+    #[test]
+    fn text_answers() {
+        assert_eq!(Answer::from("2=-1=0"), Answer::Text("2=-1=0".to_string()));
+        assert_eq!(Answer::render("#.\n.#").to_string(), "#.\n.#");
+        assert!(!Answer::Unsolved.is_solved());
+    }
+
+    #[test]
+    fn params() {
+        let mut params = Params::default();
+        params.insert("row", 10);
+
+        assert_eq!(params.get("row"), Some(10));
+        assert_eq!(params.get("bound"), None);
+    }
+}
@@ -1,19 +1,22 @@
+use crate::error::ParseError;
 use crate::io;
+use crate::solution::{Answer, Solution};
 use std::iter;
 
-pub fn part01(path: &str) -> u64 {
-    io::read_value_per_line::<u64>(path).iter().map(|&v| fuel(v)).sum()
+fn parse_input(input: &str) -> Result<Vec<u64>, ParseError> {
+    io::parse_value_per_line(input)
+}
+
+pub fn part01(masses: &[u64]) -> u64 {
+    masses.iter().map(|&v| fuel(v)).sum()
 }
 
 const fn fuel(mass: u64) -> u64 {
     (mass / 3) - 2
 }
 
-pub fn part02(path: &str) -> u64 {
-    io::read_value_per_line::<u64>(path)
-        .iter()
-        .map(|&v| fuel_with_fuel(v))
-        .sum()
+pub fn part02(masses: &[u64]) -> u64 {
+    masses.iter().map(|&v| fuel_with_fuel(v)).sum()
 }
 
 fn fuel_with_fuel(mass: u64) -> u64 {
@@ -24,17 +27,20 @@ fn fuel_with_fuel(mass: u64) -> u64 {
     .sum()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Vec<u64>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
 
-    #[test]
-    fn part01_input() {
-        assert_eq!(part01("data/y2019/day01.txt"), 3_399_394);
+    fn part1(input: &Self::Input) -> Answer {
+        part01(input).into()
     }
 
-    #[test]
-    fn part02_input() {
-        assert_eq!(part02("data/y2019/day01.txt"), 5_096_223);
+    fn part2(input: &Self::Input) -> Answer {
+        part02(input).into()
     }
 }
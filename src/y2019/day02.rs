@@ -1,15 +1,21 @@
+use crate::error::ParseError;
+use crate::solution::{Answer, Solution};
 use crate::y2019::intcode;
 
-fn read_input(path: &str) -> Vec<i32> {
-    std::fs::read_to_string(path)
-        .expect("should be able to read file")
+fn read_input(input: &str) -> Result<Vec<i32>, ParseError> {
+    input
+        .trim_end()
         .split(',')
-        .filter_map(|line| line.parse::<i32>().ok())
-        .collect::<Vec<_>>()
+        .map(|value| {
+            value.parse().map_err(|_| {
+                ParseError::new("a number").at_token(input, value)
+            })
+        })
+        .collect()
 }
 
 fn computer(memory: &[i32], noun: i32, verb: i32) -> i32 {
-    let mut computer = intcode::IntCode::new(&memory);
+    let mut computer = intcode::IntCode::new(memory);
 
     computer.replace(noun, verb);
     computer.run();
@@ -17,18 +23,14 @@ fn computer(memory: &[i32], noun: i32, verb: i32) -> i32 {
     computer.dump()[0]
 }
 
-pub fn part01(path: &str) -> i32 {
-    let memory = read_input(path);
-
-    computer(&memory, 12, 2)
+pub fn part01(memory: &[i32]) -> i32 {
+    computer(memory, 12, 2)
 }
 
-pub fn part02(path: &str) -> i32 {
-    let memory = read_input(path);
-
+pub fn part02(memory: &[i32]) -> i32 {
     for x in 0..100 {
         for y in 0..100 {
-            if computer(&memory, x, y) == 19_690_720 {
+            if computer(memory, x, y) == 19_690_720 {
                 return 100 * x + y;
             }
         }
@@ -36,17 +38,20 @@ pub fn part02(path: &str) -> i32 {
     panic!()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<i32>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_input(input)
+    }
 
-    #[test]
-    fn part01_input() {
-        assert_eq!(part01("data/y2019/day02.txt"), 3_706_713);
+    fn part1(input: &Self::Input) -> Answer {
+        part01(input).into()
     }
 
-    #[test]
-    fn part02_input() {
-        assert_eq!(part02("data/y2019/day02.txt"), 8609);
+    fn part2(input: &Self::Input) -> Answer {
+        part02(input).into()
     }
 }
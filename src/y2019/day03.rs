@@ -1,59 +1,55 @@
-use crate::grid::Position2D;
-use crate::io;
+use crate::error::ParseError;
+use crate::grid::{Direction, Position2D};
+use crate::solution::{Answer, Solution};
 use itertools::Itertools;
 use std::collections::HashSet;
 use std::iter::successors;
 
-enum Direction {
-    Up(usize),
-    Down(usize),
-    Left(usize),
-    Right(usize),
+pub struct Segment {
+    direction: Direction,
+    length: usize,
 }
 
-impl std::str::FromStr for Direction {
-    type Err = String;
+impl std::str::FromStr for Segment {
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let value = s[1..].parse().expect("expected number");
-
-        match &s[0..1] {
-            "U" => Ok(Self::Up(value)),
-            "D" => Ok(Self::Down(value)),
-            "L" => Ok(Self::Left(value)),
-            "R" => Ok(Self::Right(value)),
-            _ => Err(format!("unexpected direction {s}")),
-        }
+        let direction = s.get(0..1).unwrap_or_default().parse()?;
+        let length = s
+            .get(1..)
+            .and_then(|value| value.parse().ok())
+            .ok_or_else(|| ParseError::new("a distance").at_column(2))?;
+
+        Ok(Self { direction, length })
     }
 }
 
-impl Direction {
+impl Segment {
     fn expand(&self, p: Position2D) -> Vec<Position2D> {
-        let (step, count) = match self {
-            Self::Up(count) => (Position2D::new(0, 1), count),
-            Self::Down(count) => (Position2D::new(0, -1), count),
-            Self::Left(count) => (Position2D::new(-1, 0), count),
-            Self::Right(count) => (Position2D::new(1, 0), count),
-        };
+        let step = self.direction;
 
         successors(Some(p + step), |&point| Some(point + step))
-            .take(*count)
+            .take(self.length)
             .collect()
     }
 }
 
-fn parse_input(path: &str) -> Vec<Vec<Direction>> {
-    io::read_value_per_line::<String>(path)
-        .iter()
-        .map(|v| {
-            v.split(',')
-                .filter_map(|line| line.parse::<Direction>().ok())
+fn parse_input(input: &str) -> Result<Vec<Vec<Segment>>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(y, line)| {
+            line.split(',')
+                .map(|step| {
+                    step.parse::<Segment>()
+                        .map_err(|err| err.at_token(line, step).below(y))
+                })
                 .collect()
         })
         .collect()
 }
 
-fn extend(wire: &[Direction]) -> Vec<Position2D> {
+fn extend(wire: &[Segment]) -> Vec<Position2D> {
     wire.iter().fold(vec![Position2D::new(0, 0)], |mut acc, d| {
         let p = acc.last().expect("expected point");
         let mut positions = d.expand(*p);
@@ -64,8 +60,7 @@ fn extend(wire: &[Direction]) -> Vec<Position2D> {
     })
 }
 
-fn part01(path: &str) -> usize {
-    let input = parse_input(path);
+pub fn part01(input: &[Vec<Segment>]) -> usize {
     let start = Position2D::new(0, 0);
     let one =
         extend(&input[0]).into_iter().skip(1).collect::<HashSet<Position2D>>();
@@ -78,9 +73,7 @@ fn part01(path: &str) -> usize {
         .expect("value expected")
 }
 
-fn part02(path: &str) -> usize {
-    let input = parse_input(path);
-
+pub fn part02(input: &[Vec<Segment>]) -> usize {
     let one = extend(&input[0]);
     let two = extend(&input[1]);
 
@@ -98,17 +91,20 @@ fn part02(path: &str) -> usize {
         .expect("value expected")
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Vec<Vec<Segment>>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
 
-    #[test]
-    fn part01_input() {
-        assert_eq!(part01("data/y2019/day03.txt"), 386);
+    fn part1(input: &Self::Input) -> Answer {
+        part01(input).into()
     }
 
-    #[test]
-    fn part02_input() {
-        assert_eq!(part02("data/y2019/day03.txt"), 6484);
+    fn part2(input: &Self::Input) -> Answer {
+        part02(input).into()
     }
 }
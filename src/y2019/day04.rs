@@ -1,3 +1,5 @@
+use crate::error::ParseError;
+use crate::solution::{Answer, Solution};
 use std::collections::HashMap;
 
 fn to_digits(v: u64) -> Vec<u64> {
@@ -11,11 +13,11 @@ fn to_digits(v: u64) -> Vec<u64> {
 
     digits.into_iter().rev().collect::<Vec<_>>()
 }
-fn is_increasing(number: &Vec<u64>) -> bool {
+fn is_increasing(number: &[u64]) -> bool {
     number.windows(2).all(|el| el[0] <= el[1])
 }
 
-fn contains_pair(number: &Vec<u64>) -> bool {
+fn contains_pair(number: &[u64]) -> bool {
     number
         .iter()
         .fold(HashMap::<u64, usize>::new(), |mut acc, v| {
@@ -26,7 +28,7 @@ fn contains_pair(number: &Vec<u64>) -> bool {
         .any(|(_, &count)| count >= 2)
 }
 
-fn contains_exactly_one_pair(number: &Vec<u64>) -> bool {
+fn contains_exactly_one_pair(number: &[u64]) -> bool {
     number
         .iter()
         .fold(HashMap::<u64, usize>::new(), |mut acc, v| {
@@ -37,31 +39,46 @@ fn contains_exactly_one_pair(number: &Vec<u64>) -> bool {
         .any(|(_, &count)| count == 2)
 }
 
-fn part01(input: &[u64; 2]) -> usize {
-    (input[0]..=input[1])
+fn read_input(input: &str) -> Result<(u64, u64), ParseError> {
+    let (from, to) = input
+        .trim()
+        .split_once('-')
+        .ok_or_else(|| ParseError::new("a range like 123-456"))?;
+    let to = to
+        .parse()
+        .map_err(|_| ParseError::new("a number").at_token(input, to))?;
+
+    Ok((from.parse()?, to))
+}
+
+pub fn part01(&(from, to): &(u64, u64)) -> usize {
+    (from..=to)
         .map(to_digits)
         .filter(|v| is_increasing(v) && contains_pair(v))
         .count()
 }
 
-fn part02(input: &[u64; 2]) -> usize {
-    (input[0]..=input[1])
+pub fn part02(&(from, to): &(u64, u64)) -> usize {
+    (from..=to)
         .map(to_digits)
         .filter(|v| is_increasing(v) && contains_exactly_one_pair(v))
         .count()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = (u64, u64);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_input(input)
+    }
 
-    #[test]
-    fn part01_input() {
-        assert_eq!(part01(&[172_851, 675_869]), 1660);
+    fn part1(input: &Self::Input) -> Answer {
+        part01(input).into()
     }
 
-    #[test]
-    fn part02_input() {
-        assert_eq!(part02(&[172_851, 675_869]), 1135);
+    fn part2(input: &Self::Input) -> Answer {
+        part02(input).into()
     }
 }
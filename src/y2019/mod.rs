@@ -1,6 +1,17 @@
+use crate::registry::Puzzle;
+
 mod day01;
 mod day02;
 mod day03;
 mod day04;
 mod day05;
 mod intcode;
+
+pub fn puzzles() -> Vec<Puzzle> {
+    vec![
+        Puzzle::new::<day01::Day01>(2019, 1),
+        Puzzle::new::<day02::Day02>(2019, 2),
+        Puzzle::new::<day03::Day03>(2019, 3),
+        Puzzle::new::<day04::Day04>(2019, 4),
+    ]
+}
@@ -1,18 +1,22 @@
+use crate::error::ParseError;
 use crate::io;
+use crate::solution::{Answer, Solution};
 use std::collections::BinaryHeap;
 
-pub fn part01(path: &str) -> Result<i32, std::io::Error> {
-    let calories: Vec<Vec<i32>> = io::read_value_chunks(path);
+fn parse_input(input: &str) -> Result<Vec<Vec<i32>>, ParseError> {
+    io::parse_value_chunks(input)
+}
 
-    Ok(calories
+pub fn part01(calories: &[Vec<i32>]) -> i32 {
+    calories
         .iter()
         .map(|elf_calories| elf_calories.iter().sum::<i32>())
         .max()
-        .unwrap())
+        .unwrap()
 }
 
-pub fn part02(path: &str) -> Result<i32, std::io::Error> {
-    let mut calories: BinaryHeap<_> = io::read_value_chunks(path)
+pub fn part02(calories: &[Vec<i32>]) -> i32 {
+    let mut calories: BinaryHeap<_> = calories
         .iter()
         .map(|elf_calories| elf_calories.iter().sum::<i32>())
         .collect();
@@ -24,30 +28,23 @@ pub fn part02(path: &str) -> Result<i32, std::io::Error> {
         }
     }
 
-    Ok(top_three_sum)
+    top_three_sum
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day01;
 
-    #[test]
-    fn part01_example() {
-        assert_eq!(part01("data/y2022/day01-example.txt").unwrap(), 24_000);
-    }
+impl Solution for Day01 {
+    type Input = Vec<Vec<i32>>;
 
-    #[test]
-    fn part01_input() {
-        assert_eq!(part01("data/y2022/day01.txt").unwrap(), 69_177);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn part02_example() {
-        assert_eq!(part02("data/y2022/day01-example.txt").unwrap(), 45_000);
+    fn part1(input: &Self::Input) -> Answer {
+        part01(input).into()
     }
 
-    #[test]
-    fn part02_input() {
-        assert_eq!(part02("data/y2022/day01.txt").unwrap(), 207_456);
+    fn part2(input: &Self::Input) -> Answer {
+        part02(input).into()
     }
 }
@@ -1,4 +1,6 @@
+use crate::error::ParseError;
 use crate::io;
+use crate::solution::{Answer, Solution};
 use crate::y2022::day02::GameResult::{Draw, Lose, Win};
 use crate::y2022::day02::Shape::{Paper, Rock, Scissors};
 use std::str::FromStr;
@@ -11,14 +13,14 @@ enum Shape {
 }
 
 impl FromStr for Shape {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let shape = match s {
             "A" | "X" => Rock,
             "B" | "Y" => Paper,
             "C" | "Z" => Scissors,
-            _ => return Err(()),
+            _ => return Err(ParseError::new("a shape (A, B, C, X, Y or Z)")),
         };
 
         Ok(shape)
@@ -26,7 +28,7 @@ impl FromStr for Shape {
 }
 
 #[derive(Debug, PartialEq)]
-struct Round(Shape, Shape);
+pub struct Round(Shape, Shape);
 
 impl Round {
     fn score(&self) -> i32 {
@@ -47,17 +49,14 @@ impl Round {
 }
 
 impl FromStr for Round {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if let Some((a, b)) = s.split_once(' ') {
-            let left = a.parse::<Shape>().expect("expected shape");
-            let right = b.parse::<Shape>().expect("expected shape");
+        let (a, b) = s
+            .split_once(' ')
+            .ok_or_else(|| ParseError::new("two shapes"))?;
 
-            Ok(Round(left, right))
-        } else {
-            Err(())
-        }
+        Ok(Round(io::parse_token(s, a)?, io::parse_token(s, b)?))
     }
 }
 
@@ -69,14 +68,14 @@ enum GameResult {
 }
 
 impl FromStr for GameResult {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let result = match s {
             "X" => Lose,
             "Y" => Draw,
             "Z" => Win,
-            _ => return Err(()),
+            _ => return Err(ParseError::new("a result (X, Y or Z)")),
         };
 
         Ok(result)
@@ -84,7 +83,7 @@ impl FromStr for GameResult {
 }
 
 #[derive(Debug, PartialEq)]
-struct FixedScore(Shape, GameResult);
+pub struct FixedScore(Shape, GameResult);
 
 impl FixedScore {
     fn score(&self) -> i32 {
@@ -106,55 +105,46 @@ impl FixedScore {
 }
 
 impl FromStr for FixedScore {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if let Some((a, b)) = s.split_once(' ') {
-            let shape = a.parse::<Shape>().expect("expect shape");
-            let result = b.parse::<GameResult>().expect("result");
+        let (a, b) = s
+            .split_once(' ')
+            .ok_or_else(|| ParseError::new("a shape and a result"))?;
 
-            Ok(FixedScore(shape, result))
-        } else {
-            Err(())
-        }
+        Ok(FixedScore(io::parse_token(s, a)?, io::parse_token(s, b)?))
     }
 }
 
-fn part01(path: &str) -> i32 {
-    io::read_value_per_line::<Round>(path)
-        .iter()
-        .map(|r| r.score())
-        .sum()
+fn parse_input(input: &str) -> Result<(Vec<Round>, Vec<FixedScore>), ParseError> {
+    Ok((
+        io::parse_value_per_line(input)?,
+        io::parse_value_per_line(input)?,
+    ))
 }
 
-fn part02(path: &str) -> i32 {
-    io::read_value_per_line::<FixedScore>(path)
-        .iter()
-        .map(|r| r.score())
-        .sum()
+pub fn part01(rounds: &[Round]) -> i32 {
+    rounds.iter().map(|r| r.score()).sum()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub fn part02(rounds: &[FixedScore]) -> i32 {
+    rounds.iter().map(|r| r.score()).sum()
+}
 
-    #[test]
-    fn part01_example() {
-        assert_eq!(part01("data/y2022/day02-example.txt"), 15);
-    }
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = (Vec<Round>, Vec<FixedScore>);
 
-    #[test]
-    fn part01_input() {
-        assert_eq!(part01("data/y2022/day02.txt"), 11_475);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn part02_example() {
-        assert_eq!(part02("data/y2022/day02-example.txt"), 12);
+    fn part1(input: &Self::Input) -> Answer {
+        part01(&input.0).into()
     }
 
-    #[test]
-    fn part02_input() {
-        assert_eq!(part02("data/y2022/day02.txt"), 16862);
+    fn part2(input: &Self::Input) -> Answer {
+        part02(&input.1).into()
     }
 }
@@ -1,11 +1,15 @@
+use crate::error::ParseError;
+use crate::io;
+use crate::solution::{Answer, Solution};
 use std::collections::HashSet;
 
-pub fn read_input(filename: &str) -> Vec<String> {
-    std::fs::read_to_string(filename)
-        .expect("should be able to read")
-        .lines()
-        .map(|line| line.to_string())
-        .collect()
+pub fn read_input(input: &str) -> Result<Vec<String>, ParseError> {
+    io::parse_lines(input, |line| {
+        match line.chars().position(|c| !c.is_ascii_alphabetic()) {
+            Some(x) => Err(ParseError::new("an item (a-z or A-Z)").at_column(x + 1)),
+            None => Ok(line.to_string()),
+        }
+    })
 }
 
 pub fn priority(item: &char) -> i32 {
@@ -16,8 +20,8 @@ pub fn priority(item: &char) -> i32 {
     }
 }
 
-pub fn part01(filename: &str) -> i32 {
-    read_input(filename)
+pub fn part01(rucksacks: &[String]) -> i32 {
+    rucksacks
         .iter()
         .map(|line| {
             let (a, b) = line.split_at(line.len() / 2);
@@ -30,8 +34,8 @@ pub fn part01(filename: &str) -> i32 {
         .sum::<i32>()
 }
 
-pub fn part02(filename: &str) -> i32 {
-    read_input(filename)
+pub fn part02(rucksacks: &[String]) -> i32 {
+    rucksacks
         .chunks(3)
         .map(|chunk| {
             let mut chunks = chunk.iter().map(|e| e.chars().collect::<HashSet<_>>());
@@ -51,27 +55,20 @@ pub fn part02(filename: &str) -> i32 {
         .sum()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day03;
 
-    #[test]
-    fn part01_example() {
-        assert_eq!(part01("data/y2022/day03-example.txt"), 157);
-    }
+impl Solution for Day03 {
+    type Input = Vec<String>;
 
-    #[test]
-    fn part01_input() {
-        assert_eq!(part01("data/y2022/day03.txt"), 8233);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_input(input)
     }
 
-    #[test]
-    fn part02_example() {
-        assert_eq!(part02("data/y2022/day03-example.txt"), 70);
+    fn part1(input: &Self::Input) -> Answer {
+        part01(input).into()
     }
 
-    #[test]
-    fn part02_input() {
-        assert_eq!(part02("data/y2022/day03.txt"), 2821);
+    fn part2(input: &Self::Input) -> Answer {
+        part02(input).into()
     }
 }
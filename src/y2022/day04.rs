@@ -1,20 +1,20 @@
+use crate::error::ParseError;
+use crate::io;
+use crate::solution::{Answer, Solution};
 use std::str::FromStr;
 
 #[derive(Debug, PartialEq)]
-struct Range(i32, i32);
+pub struct Range(i32, i32);
 
 impl FromStr for Range {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if let Some((a, b)) = s.split_once('-') {
-            let start = a.parse::<i32>().expect("expected number");
-            let end = b.parse::<i32>().expect("expected number");
+        let (a, b) = s
+            .split_once('-')
+            .ok_or_else(|| ParseError::new("a range like 2-4"))?;
 
-            Ok(Range(start, end))
-        } else {
-            Err(())
-        }
+        Ok(Range(io::parse_token(s, a)?, io::parse_token(s, b)?))
     }
 }
 
@@ -28,53 +28,38 @@ impl Range {
     }
 }
 
-fn read_input(filename: &str) -> Vec<(Range, Range)> {
-    std::fs::read_to_string(filename)
-        .expect("should be able to read")
-        .lines()
-        .map(|line| {
-            let (a, b) = line.split_once(',').unwrap();
+fn read_input(input: &str) -> Result<Vec<(Range, Range)>, ParseError> {
+    io::parse_lines(input, |line| {
+        let (a, b) = line
+            .split_once(',')
+            .ok_or_else(|| ParseError::new("a pair of ranges"))?;
 
-            (a.parse::<Range>().unwrap(), b.parse::<Range>().unwrap())
-        })
-        .collect()
+        Ok((io::parse_token(line, a)?, io::parse_token(line, b)?))
+    })
 }
 
-fn part01(filename: &str) -> usize {
-    read_input(filename)
-        .iter()
-        .filter(|(a, b)| a.contains(b))
-        .count()
+pub fn part01(pairs: &[(Range, Range)]) -> usize {
+    pairs.iter().filter(|(a, b)| a.contains(b)).count()
 }
 
-fn part02(filename: &str) -> usize {
-    read_input(filename)
-        .iter()
-        .filter(|(a, b)| a.overlaps(b))
-        .count()
+pub fn part02(pairs: &[(Range, Range)]) -> usize {
+    pairs.iter().filter(|(a, b)| a.overlaps(b)).count()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day04;
 
-    #[test]
-    fn part01_example() {
-        assert_eq!(part01("data/y2022/day04-example.txt"), 2);
-    }
+impl Solution for Day04 {
+    type Input = Vec<(Range, Range)>;
 
-    #[test]
-    fn part01_input() {
-        assert_eq!(part01("data/y2022/day04.txt"), 569);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_input(input)
     }
 
-    #[test]
-    fn part02_example() {
-        assert_eq!(part02("data/y2022/day04-example.txt"), 4);
+    fn part1(input: &Self::Input) -> Answer {
+        part01(input).into()
     }
 
-    #[test]
-    fn part02_input() {
-        assert_eq!(part02("data/y2022/day04.txt"), 936);
+    fn part2(input: &Self::Input) -> Answer {
+        part02(input).into()
     }
 }
@@ -1,53 +1,85 @@
+use crate::error::ParseError;
+use crate::io;
+use crate::solution::{Answer, Solution};
 use std::collections::VecDeque;
 use std::str::FromStr;
 
 #[derive(Debug, PartialEq)]
-struct Move(usize, usize, usize);
+pub struct Move(usize, usize, usize);
 
 impl FromStr for Move {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let words = s.split(' ').collect::<Vec<&str>>();
 
-        let qty = words.get(1).unwrap().parse::<usize>().unwrap();
-        let from = words.get(3).unwrap().parse::<usize>().unwrap();
-        let to = words.get(5).unwrap().parse::<usize>().unwrap();
+        let ["move", qty, "from", from, "to", to] = words[..] else {
+            return Err(ParseError::new("move <qty> from <stack> to <stack>"));
+        };
 
-        Ok(Move(qty, from - 1, to - 1))
+        let stack = |word: &str| {
+            io::parse_token::<usize>(s, word)?
+                .checked_sub(1)
+                .ok_or_else(|| ParseError::new("a stack number from 1").at_token(s, word))
+        };
+
+        Ok(Move(io::parse_token(s, qty)?, stack(from)?, stack(to)?))
     }
 }
 
-fn read_input(filename: &str) -> (Vec<VecDeque<char>>, Vec<Move>) {
-    let input = std::fs::read_to_string(filename)
-        .expect("expected file")
-        .split('\n')
-        .map(|line| line.lines().map(|l| l.to_string()).collect())
-        .collect::<Vec<String>>();
+type Procedure = (Vec<VecDeque<char>>, Vec<Move>);
 
-    let mut input_iter = input.iter();
+// the puzzle draws the stacks as columns of [X] crates above a row of labels
+fn parse_drawing(drawing: &str) -> Result<Vec<VecDeque<char>>, ParseError> {
+    let lines = drawing.lines().collect::<Vec<_>>();
+    let Some((labels, rows)) = lines.split_last() else {
+        return Err(ParseError::new("a drawing of the stacks"));
+    };
 
-    let stack_lines = input_iter
-        .by_ref()
-        .take_while(|line| !line.is_empty())
-        .cloned()
-        .collect::<Vec<String>>();
+    let mut stacks = vec![VecDeque::new(); labels.split_whitespace().count()];
 
-    let stacks = stack_lines.iter().fold(Vec::new(), |mut acc, line| {
-        acc.push(line.chars().rev().collect::<VecDeque<char>>());
-        acc
-    });
+    for (y, row) in rows.iter().enumerate() {
+        for (i, stack) in stacks.iter_mut().enumerate() {
+            match row.chars().nth(1 + 4 * i) {
+                Some(c) if c.is_ascii_uppercase() => stack.push_back(c),
+                Some(' ') | None => {}
+                Some(_) => return Err(ParseError::new("a crate (A-Z)").at(y + 1, 2 + 4 * i)),
+            }
+        }
+    }
 
-    let moves = input_iter
-        .cloned()
-        .filter_map(|line| line.parse::<Move>().ok())
-        .collect::<Vec<Move>>();
+    Ok(stacks)
+}
 
-    (stacks, moves)
+fn read_input(input: &str) -> Result<Procedure, ParseError> {
+    let (stack_lines, move_lines) = input
+        .split_once("\n\n")
+        .ok_or_else(|| ParseError::new("stacks and moves"))?;
+
+    let is_drawing = stack_lines
+        .lines()
+        .last()
+        .is_some_and(|line| line.trim_start().starts_with('1'));
+
+    let stacks = if is_drawing {
+        parse_drawing(stack_lines)?
+    } else {
+        io::parse_lines(stack_lines, |line| {
+            match line.chars().position(|c| !c.is_ascii_uppercase()) {
+                Some(x) => Err(ParseError::new("a crate (A-Z)").at_column(x + 1)),
+                None => Ok(line.chars().rev().collect::<VecDeque<char>>()),
+            }
+        })?
+    };
+
+    let moves = io::parse_value_per_line(move_lines)
+        .map_err(|err: ParseError| err.below(stack_lines.lines().count() + 1))?;
+
+    Ok((stacks, moves))
 }
 
-fn part01(filename: &str) -> String {
-    let (mut stacks, moves) = read_input(filename);
+pub fn part01((stacks, moves): &Procedure) -> String {
+    let mut stacks = stacks.clone();
 
     for m in moves {
         for _ in 0..m.0 {
@@ -64,8 +96,8 @@ fn part01(filename: &str) -> String {
     result
 }
 
-fn part02(filename: &str) -> String {
-    let (mut stacks, moves) = read_input(filename);
+pub fn part02((stacks, moves): &Procedure) -> String {
+    let mut stacks = stacks.clone();
 
     for m in moves {
         let mut v = Vec::new();
@@ -86,47 +118,20 @@ fn part02(filename: &str) -> String {
     result
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day05;
 
-    #[test]
-    fn part01_example() {
-        let foo = part01("data/y2022/day05-example-modified.txt");
+impl Solution for Day05 {
+    type Input = Procedure;
 
-        println!("{:?}", foo);
-
-        //assert_eq!(foo.first().unwrap(),);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_input(input)
     }
 
-    #[test]
-    fn part01_input() {
-        // FZCMJCRHZ
-
-        let foo = part01("data/y2022/day05-modified.txt");
-
-        println!("{:?}", foo);
-
-        //assert_eq!(foo.first().unwrap(),);
+    fn part1(input: &Self::Input) -> Answer {
+        part01(input).into()
     }
 
-    #[test]
-    fn part02_example() {
-        let foo = part02("data/y2022/day05-example-modified.txt");
-
-        println!("{:?}", foo);
-
-        //assert_eq!(foo.first().unwrap(),);
-    }
-
-    #[test]
-    fn part02_input() {
-        // JSDHQMZGF
-
-        let foo = part02("data/y2022/day05-modified.txt");
-
-        println!("{:?}", foo);
-
-        //assert_eq!(foo.first().unwrap(),);
+    fn part2(input: &Self::Input) -> Answer {
+        part02(input).into()
     }
 }
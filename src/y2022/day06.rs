@@ -1,6 +1,8 @@
+use crate::error::ParseError;
+use crate::solution::{Answer, Solution};
 use std::collections::HashSet;
 
-fn find_marker(window: usize, s: String) -> usize {
+fn find_marker(window: usize, s: &str) -> usize {
     let res = s
         .bytes()
         .collect::<Vec<u8>>()
@@ -14,65 +16,57 @@ fn find_marker(window: usize, s: String) -> usize {
     res + window
 }
 
-fn part01(filename: &str) -> usize {
-    find_marker(4, std::fs::read_to_string(filename).expect("expected file"))
+fn read_input(input: &str) -> Result<String, ParseError> {
+    Ok(input.trim().to_string())
 }
 
-fn part02(filename: &str) -> usize {
-    find_marker(
-        14,
-        std::fs::read_to_string(filename).expect("expected file"),
-    )
+pub fn part01(signal: &str) -> usize {
+    find_marker(4, signal)
+}
+
+pub fn part02(signal: &str) -> usize {
+    find_marker(14, signal)
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        part01(input).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        part02(input).into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn part01_example() {
-        let _foo = part01("data/y2022/day06.txt");
-    }
-
     #[test]
     fn part01_example_1() {
-        assert_eq!(
-            find_marker(4, String::from("bvwbjplbgvbhsrlpgdmjqwftvncz")),
-            5
-        );
+        assert_eq!(find_marker(4, "bvwbjplbgvbhsrlpgdmjqwftvncz"), 5);
     }
 
     #[test]
     fn part01_example_2() {
-        assert_eq!(
-            find_marker(4, String::from("nppdvjthqldpwncqszvftbrmjlhg")),
-            6
-        );
+        assert_eq!(find_marker(4, "nppdvjthqldpwncqszvftbrmjlhg"), 6);
     }
 
     #[test]
     fn part01_example_3() {
-        assert_eq!(
-            find_marker(4, String::from("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg")),
-            10
-        );
-    }
-
-    #[test]
-    fn part01_input() {
-        assert_eq!(part01("data/y2022/day06.txt"), 1578);
+        assert_eq!(find_marker(4, "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), 10);
     }
 
     #[test]
     fn part02_example_3() {
-        assert_eq!(
-            find_marker(14, String::from("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg")),
-            29
-        );
-    }
-
-    #[test]
-    fn part02_input() {
-        assert_eq!(part02("data/y2022/day06.txt"), 2178);
+        assert_eq!(find_marker(14, "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), 29);
     }
 }
@@ -1,11 +1,13 @@
+use crate::error::ParseError;
 use std::collections::HashMap;
 use std::str::FromStr;
 
 use crate::io;
+use crate::solution::{Answer, Solution};
 use crate::y2022::day07::Output::{Cd, Dir, File, Ls};
 
 #[derive(Debug, PartialEq)]
-enum Output {
+pub enum Output {
     Ls,
     Cd(String),
     File(String, i32),
@@ -13,30 +15,27 @@ enum Output {
 }
 
 impl FromStr for Output {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let res = match s {
-            "$ ls" => Ls,
-            i if i.starts_with("$ cd") => {
-                let d = s.strip_prefix("$ cd ").unwrap();
-                Cd(d.to_string())
-            }
-            i if i.starts_with("dir") => {
-                let d = s.strip_prefix("dir ").unwrap();
-                Dir(d.to_string())
-            }
-            _ => {
-                let (size, f) = s.split_once(' ').unwrap();
-                File(f.to_string(), size.parse().unwrap())
-            }
+        let res = if s == "$ ls" {
+            Ls
+        } else if let Some(d) = s.strip_prefix("$ cd ") {
+            Cd(d.to_string())
+        } else if let Some(d) = s.strip_prefix("dir ") {
+            Dir(d.to_string())
+        } else {
+            let (size, f) = s
+                .split_once(' ')
+                .ok_or_else(|| ParseError::new("a command, a directory or a file"))?;
+            File(f.to_string(), io::parse_token(s, size)?)
         };
 
         Ok(res)
     }
 }
 
-fn directory_sizes(output: Vec<Output>) -> HashMap<Vec<String>, i32> {
+fn directory_sizes(output: &[Output]) -> HashMap<Vec<String>, i32> {
     let mut directories = HashMap::new();
     let mut current_path = Vec::new();
 
@@ -48,7 +47,7 @@ fn directory_sizes(output: Vec<Output>) -> HashMap<Vec<String>, i32> {
                 if dir == ".." {
                     current_path.pop();
                 } else {
-                    current_path.push(dir);
+                    current_path.push(dir.clone());
                 }
             }
             File(_, size) => {
@@ -59,7 +58,7 @@ fn directory_sizes(output: Vec<Output>) -> HashMap<Vec<String>, i32> {
                         continue;
                     }
 
-                    *directories.entry(path).or_insert(0) += size;
+                    *directories.entry(path).or_insert(0) += *size;
                 }
             }
         }
@@ -67,18 +66,18 @@ fn directory_sizes(output: Vec<Output>) -> HashMap<Vec<String>, i32> {
     directories
 }
 
-fn part01(filename: &str) -> i32 {
-    let output = io::read_value_per_line::<Output>(filename);
+fn parse_input(input: &str) -> Result<Vec<Output>, ParseError> {
+    io::parse_value_per_line(input)
+}
 
+pub fn part01(output: &[Output]) -> i32 {
     directory_sizes(output)
         .values()
         .filter(|&v| *v <= 100_000)
         .sum()
 }
 
-fn part02(filename: &str) -> i32 {
-    let output = io::read_value_per_line::<Output>(filename);
-
+pub fn part02(output: &[Output]) -> i32 {
     let mut sizes = directory_sizes(output)
         .values()
         .cloned()
@@ -91,27 +90,20 @@ fn part02(filename: &str) -> i32 {
     *sizes.iter().find(|&i| unused + *i >= 30000000).unwrap()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day07;
 
-    #[test]
-    fn part01_example() {
-        assert_eq!(part01("data/y2022/day07-example.txt"), 95437);
-    }
+impl Solution for Day07 {
+    type Input = Vec<Output>;
 
-    #[test]
-    fn part01_input() {
-        assert_eq!(part01("data/y2022/day07.txt"), 1427048);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn part02_example() {
-        assert_eq!(part02("data/y2022/day07-example.txt"), 24933642);
+    fn part1(input: &Self::Input) -> Answer {
+        part01(input).into()
     }
 
-    #[test]
-    fn part02_input() {
-        assert_eq!(part02("data/y2022/day07.txt"), 2940614);
+    fn part2(input: &Self::Input) -> Answer {
+        part02(input).into()
     }
 }
@@ -1,115 +1,82 @@
+use crate::error::ParseError;
+use crate::grid::{Grid, Position2D};
+use crate::io;
+use crate::solution::{Answer, Solution};
 use std::ops::ControlFlow;
 
-fn read_input(filename: &str) -> Vec<Vec<u32>> {
-    std::fs::read_to_string(filename)
-        .expect("should be able to read file")
-        .lines()
-        .map(|line| line.chars().filter_map(|c| c.to_digit(10)).collect())
-        .collect()
+fn read_input(input: &str) -> Result<Grid<u32>, ParseError> {
+    Grid::from_rows(io::parse_lines(input, |line| {
+        line.chars()
+            .enumerate()
+            .map(|(x, c)| {
+                c.to_digit(10)
+                    .ok_or_else(|| ParseError::new("a tree height (0-9)").at_column(x + 1))
+            })
+            .collect()
+    })?)
 }
 
-type Position = (usize, usize);
-type Bounds = (usize, usize);
-
-fn get_neighbours(b: Bounds, p: Position) -> Vec<Vec<Position>> {
-    let y_range_before = (0..p.1).rev().map(|y| (p.0, y)).collect::<Vec<Position>>();
-
-    let y_range_after = (p.1 + 1..b.1).map(|y| (p.0, y)).collect::<Vec<Position>>();
-
-    let x_range_before = (0..p.0).rev().map(|x| (x, p.1)).collect::<Vec<Position>>();
-
-    let x_range_after = (p.0 + 1..b.0).map(|x| (x, p.1)).collect::<Vec<Position>>();
-
-    vec![y_range_before, y_range_after, x_range_before, x_range_after]
+// the trees seen from `p` looking up, down, left and right
+fn lines_of_sight(trees: &Grid<u32>, p: Position2D) -> Vec<Vec<u32>> {
+    [(0, -1), (0, 1), (-1, 0), (1, 0)]
+        .into_iter()
+        .map(|(dx, dy)| {
+            let step = Position2D::new(dx, dy);
+
+            std::iter::successors(Some(p + step), |&n| Some(n + step))
+                .map_while(|n| trees.get(n).copied())
+                .collect()
+        })
+        .collect()
 }
 
-fn part01(filename: &str) -> u32 {
-    let trees = read_input(filename);
-    let rows = trees.len();
-    let columns = trees.get(0).unwrap().len();
-
-    let mut count = 0;
-    for y in 0..rows {
-        for x in 0..columns {
-            if x == 0 || y == 0 || x == columns - 1 || y == rows - 1 {
-                count += 1;
-                continue;
-            }
-
-            let n = get_neighbours((columns, rows), (x, y));
-            let height = trees[y][x];
-
-            for i in n {
-                let visible = i.iter().map(|&(x, y)| trees[y][x]).all(|h| h < height);
-
-                if visible {
-                    count += 1;
-                    break;
-                }
-            }
-        }
-    }
-
-    count
+pub fn part01(trees: &Grid<u32>) -> usize {
+    trees
+        .iter()
+        .filter(|&(p, &height)| {
+            lines_of_sight(trees, p)
+                .iter()
+                .any(|line| line.iter().all(|&h| h < height))
+        })
+        .count()
 }
 
-fn part02(filename: &str) -> i32 {
-    let trees = read_input(filename);
-    let rows = trees.len();
-    let columns = trees.get(0).unwrap().len();
-
-    let mut max = 0;
-    for y in 0..rows {
-        for x in 0..columns {
-            let n = get_neighbours((columns, rows), (x, y));
-            let height = trees[y][x];
-
-            let scenic_score = n
+pub fn part02(trees: &Grid<u32>) -> i32 {
+    trees
+        .iter()
+        .map(|(p, &height)| {
+            lines_of_sight(trees, p)
                 .iter()
-                .map(|c| {
-                    c.iter()
-                        .map(|&(x, y)| trees[y][x])
-                        .try_fold(0, |acc, h| match h {
-                            h if h < height => ControlFlow::Continue(acc + 1),
-                            _ => ControlFlow::Break(acc + 1),
-                        })
+                .map(|line| {
+                    line.iter().try_fold(0, |acc, &h| match h {
+                        h if h < height => ControlFlow::Continue(acc + 1),
+                        _ => ControlFlow::Break(acc + 1),
+                    })
                 })
                 .map(|v| match v {
                     ControlFlow::Continue(a) => a,
                     ControlFlow::Break(b) => b,
                 })
-                .product();
-
-            if scenic_score > max {
-                max = scenic_score;
-            }
-        }
-    }
-
-    max
+                .product()
+        })
+        .max()
+        .unwrap_or(0)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day08;
 
-    #[test]
-    fn part01_example() {
-        assert_eq!(part01("data/y2022/day08-example.txt"), 21);
-    }
+impl Solution for Day08 {
+    type Input = Grid<u32>;
 
-    #[test]
-    fn part01_input() {
-        assert_eq!(part01("data/y2022/day08.txt"), 1801);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_input(input)
     }
 
-    #[test]
-    fn part02_example() {
-        assert_eq!(part02("data/y2022/day08-example.txt"), 8);
+    fn part1(input: &Self::Input) -> Answer {
+        part01(input).into()
     }
 
-    #[test]
-    fn part02_input() {
-        assert_eq!(part02("data/y2022/day08.txt"), 209880);
+    fn part2(input: &Self::Input) -> Answer {
+        part02(input).into()
     }
 }
@@ -1,75 +1,52 @@
+use crate::error::ParseError;
+use crate::grid::{Direction, Position2D};
 use crate::io;
+use crate::solution::{Answer, Solution};
 use std::{collections::HashSet, str::FromStr};
 
-struct Move(Position, i32);
+pub struct Move(Direction, i32);
 
 impl FromStr for Move {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        fn parse_digit(s: &str) -> i32 {
-            let (_, d) = s.split_once(' ').unwrap();
+        let (direction, steps) = s
+            .split_once(' ')
+            .ok_or_else(|| ParseError::new("a direction and a number of steps"))?;
 
-            d.parse::<i32>().unwrap()
-        }
-
-        let res = match s {
-            i if i.starts_with('U') => Self((0, 1), parse_digit(s)),
-            i if i.starts_with('D') => Self((0, -1), parse_digit(s)),
-            i if i.starts_with('L') => Self((-1, 0), parse_digit(s)),
-            i if i.starts_with('R') => Self((1, 0), parse_digit(s)),
-            _ => panic!("unreachble"),
-        };
-
-        Ok(res)
+        Ok(Self(direction.parse()?, io::parse_token(s, steps)?))
     }
 }
 
-type Position = (i32, i32);
-
-fn is_connected(lhs: &Position, rhs: &Position) -> bool {
-    let dx = if lhs.0 > rhs.0 {
-        lhs.0 - rhs.0
-    } else {
-        rhs.0 - lhs.0
-    };
-
-    let dy = if lhs.1 > rhs.1 {
-        lhs.1 - rhs.1
-    } else {
-        rhs.1 - lhs.1
-    };
-
-    dx <= 1 && dy <= 1
+fn is_connected(lhs: &Position2D, rhs: &Position2D) -> bool {
+    (lhs.x - rhs.x).abs() <= 1 && (lhs.y - rhs.y).abs() <= 1
 }
 
 #[derive(Debug, PartialEq)]
 struct Rope {
-    knots: Vec<Position>,
-    visited: HashSet<Position>,
+    knots: Vec<Position2D>,
+    visited: HashSet<Position2D>,
 }
 
 impl Rope {
     fn new(knots: usize) -> Self {
         Self {
-            knots: vec![(0, 0); knots],
+            knots: vec![Position2D::new(0, 0); knots],
             visited: HashSet::new(),
         }
     }
 
-    fn motion(&mut self, m: Move) {
-        let Move(offset, count) = m;
+    fn motion(&mut self, m: &Move) {
+        let &Move(direction, count) = m;
 
         for _ in 0..count {
-            self.knots[0].0 += offset.0;
-            self.knots[0].1 += offset.1;
+            self.knots[0] += direction;
 
             for i in 1..self.knots.len() {
                 if !is_connected(&self.knots[i], &self.knots[i - 1]) {
-                    let dx = self.knots[i - 1].0 - self.knots[i].0;
-                    let dy = self.knots[i - 1].1 - self.knots[i].1;
+                    let delta = self.knots[i - 1] - self.knots[i];
 
-                    self.knots[i] = (self.knots[i].0 + dx.signum(), self.knots[i].1 + dy.signum());
+                    self.knots[i] += Position2D::new(delta.x.signum(), delta.y.signum());
                 }
             }
 
@@ -78,10 +55,13 @@ impl Rope {
     }
 }
 
-fn part01(filename: &str) -> usize {
-    let foo = io::read_value_per_line::<Move>(filename);
+fn parse_input(input: &str) -> Result<Vec<Move>, ParseError> {
+    io::parse_value_per_line(input)
+}
 
-    foo.into_iter()
+pub fn part01(moves: &[Move]) -> usize {
+    moves
+        .iter()
         .fold(Rope::new(2), |mut r, m| {
             r.motion(m);
             r
@@ -90,10 +70,9 @@ fn part01(filename: &str) -> usize {
         .len()
 }
 
-fn part02(filename: &str) -> usize {
-    let foo = io::read_value_per_line::<Move>(filename);
-
-    foo.into_iter()
+pub fn part02(moves: &[Move]) -> usize {
+    moves
+        .iter()
         .fold(Rope::new(10), |mut r, m| {
             r.motion(m);
             r
@@ -102,27 +81,20 @@ fn part02(filename: &str) -> usize {
         .len()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day09;
 
-    #[test]
-    fn part01_example() {
-        assert_eq!(part01("data/y2022/day09-example.txt"), 13);
-    }
+impl Solution for Day09 {
+    type Input = Vec<Move>;
 
-    #[test]
-    fn part01_input() {
-        assert_eq!(part01("data/y2022/day09.txt"), 6269);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn part02_example() {
-        assert_eq!(part02("data/y2022/day09-example.txt"), 1);
+    fn part1(input: &Self::Input) -> Answer {
+        part01(input).into()
     }
 
-    #[test]
-    fn part02_input() {
-        assert_eq!(part02("data/y2022/day09.txt"), 2557);
+    fn part2(input: &Self::Input) -> Answer {
+        part02(input).into()
     }
 }
@@ -1,32 +1,32 @@
+use crate::error::ParseError;
 use std::{str::FromStr, vec};
 
 use crate::io;
+use crate::ocr;
+use crate::solution::{Answer, Solution};
 
 #[derive(Debug, PartialEq, Clone)]
-enum Instruction {
+pub enum Instruction {
     Noop,
     Addx(i32),
 }
 
 impl FromStr for Instruction {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let res = match s {
-            "noop" => Self::Noop,
-            _ => {
-                let (_, d) = s.split_once(' ').unwrap();
-
-                Self::Addx(d.parse::<i32>().unwrap())
-            }
+        let res = match s.split_once(' ') {
+            None if s == "noop" => Self::Noop,
+            Some(("addx", d)) => Self::Addx(io::parse_token(s, d)?),
+            _ => return Err(ParseError::new("noop or addx <value>")),
         };
 
         Ok(res)
     }
 }
 
-fn cycle_values(instructions: Vec<Instruction>) -> Vec<i32> {
-    instructions.into_iter().fold(vec![1], |mut values, i| {
+fn cycle_values(instructions: &[Instruction]) -> Vec<i32> {
+    instructions.iter().fold(vec![1], |mut values, i| {
         let x = values[values.len() - 1];
 
         match i {
@@ -44,8 +44,12 @@ fn cycle_values(instructions: Vec<Instruction>) -> Vec<i32> {
     })
 }
 
-fn part01(filename: &str) -> i32 {
-    let register_x = cycle_values(io::read_value_per_line::<Instruction>(filename));
+fn parse_input(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    io::parse_value_per_line(input)
+}
+
+pub fn part01(instructions: &[Instruction]) -> i32 {
+    let register_x = cycle_values(instructions);
 
     let mut sum = 0;
     for cycle in [20, 60, 100, 140, 180, 220].iter() {
@@ -55,8 +59,8 @@ fn part01(filename: &str) -> i32 {
     sum
 }
 
-fn part02(filename: &str) {
-    let register_x = cycle_values(io::read_value_per_line::<Instruction>(filename));
+pub fn part02(instructions: &[Instruction]) -> String {
+    let register_x = cycle_values(instructions);
 
     let crt = (0..=240)
         .map(|cycle| {
@@ -70,34 +74,30 @@ fn part02(filename: &str) {
         })
         .collect::<Vec<char>>();
 
-    for line in crt.chunks(40) {
-        println!("{}", line.iter().collect::<String>());
-    }
+    crt.chunks(40)
+        .take(6)
+        .map(|line| line.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day10;
 
-    #[test]
-    fn part01_example() {
-        assert_eq!(part01("data/y2022/day10-example.txt"), 13140);
-    }
+impl Solution for Day10 {
+    type Input = Vec<Instruction>;
 
-    #[test]
-    fn part01_input() {
-        assert_eq!(part01("data/y2022/day10.txt"), 14240);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    #[ignore]
-    fn part02_example() {
-        part02("data/y2022/day10-example.txt");
+    fn part1(input: &Self::Input) -> Answer {
+        part01(input).into()
     }
 
-    #[test]
-    #[ignore = "PLULKBZH"]
-    fn part02_input() {
-        part02("data/y2022/day10.txt");
+    // the crt spells out letters, though the example only draws stripes
+    fn part2(input: &Self::Input) -> Answer {
+        let crt = part02(input);
+
+        ocr::read_str(&crt).map_or_else(|| Answer::render(crt), Answer::from)
     }
 }
@@ -1,6 +1,8 @@
+use crate::error::ParseError;
 use std::{cmp, str::FromStr};
 
 use crate::io;
+use crate::solution::{Answer, Solution};
 
 #[derive(Debug, Clone, Copy)]
 enum Operation {
@@ -11,17 +13,15 @@ enum Operation {
 }
 
 impl FromStr for Operation {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (op, v) = s.split_once(' ').unwrap();
-
-        let operation = match (op, v) {
-            ("*", "old") => Operation::MultiplySelf,
-            ("*", v) => Operation::Multiply(v.parse::<u64>().unwrap()),
-            ("+", "old") => Operation::AddSelf,
-            ("+", v) => Operation::Add(v.parse::<u64>().unwrap()),
-            _ => panic!("unreachable"),
+        let operation = match s.split_once(' ') {
+            Some(("*", "old")) => Operation::MultiplySelf,
+            Some(("*", v)) => Operation::Multiply(io::parse_token(s, v)?),
+            Some(("+", "old")) => Operation::AddSelf,
+            Some(("+", v)) => Operation::Add(io::parse_token(s, v)?),
+            _ => return Err(ParseError::new("an operation like * 19 or + old")),
         };
 
         Ok(operation)
@@ -29,7 +29,7 @@ impl FromStr for Operation {
 }
 
 #[derive(Debug, Clone)]
-struct Monkey {
+pub struct Monkey {
     items: Vec<u64>,
     operation: Operation,
     test_value: u64,
@@ -38,61 +38,52 @@ struct Monkey {
     inpections: usize,
 }
 
-fn parse_input(path: &str) -> Vec<Monkey> {
-    let monkey_lines = io::read_value_chunks::<String>(path);
-
-    let mut monkeys: Vec<Monkey> = Vec::with_capacity(monkey_lines.len());
-
-    for line in monkey_lines {
-        let items = line[1]
-            .trim_start()
-            .strip_prefix("Starting items: ")
-            .unwrap()
-            .split(", ")
-            .filter_map(|v| v.parse::<u64>().ok())
-            .collect::<Vec<_>>();
-
-        let operation = line[2]
-            .trim_start()
-            .strip_prefix("Operation: new = old ")
-            .unwrap()
-            .parse::<Operation>()
-            .unwrap();
-
-        let test_value = line[3]
-            .trim_start()
-            .strip_prefix("Test: divisible by ")
-            .unwrap()
-            .parse::<u64>()
-            .unwrap();
-
-        let on_true_monkey = line[4]
-            .trim_start()
-            .strip_prefix("If true: throw to monkey ")
-            .unwrap()
-            .parse::<usize>()
-            .unwrap();
-
-        let on_false_monkey = line[5]
-            .trim_start()
-            .strip_prefix("If false: throw to monkey ")
-            .unwrap()
-            .parse::<usize>()
-            .unwrap();
-
-        let m = Monkey {
-            items,
-            operation,
-            test_value,
-            on_true_monkey,
-            on_false_monkey,
-            inpections: 0,
-        };
+fn field<'a>(lines: &[&'a str], index: usize, prefix: &str) -> Result<&'a str, ParseError> {
+    let line = lines.get(index).copied().unwrap_or_default();
+    let trimmed = line.trim_start();
 
-        monkeys.push(m);
-    }
+    trimmed.strip_prefix(prefix).ok_or_else(|| {
+        ParseError::new(format!("\"{prefix}\""))
+            .at_token(line, trimmed)
+            .below(index)
+    })
+}
+
+fn number<T>(lines: &[&str], index: usize, prefix: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: Into<ParseError>,
+{
+    let value = field(lines, index, prefix)?;
+
+    io::parse_token(lines[index], value).map_err(|err| err.below(index))
+}
+
+fn parse_monkey(chunk: &str) -> Result<Monkey, ParseError> {
+    let lines = chunk.lines().collect::<Vec<_>>();
+
+    let items = field(&lines, 1, "Starting items: ")?
+        .split(", ")
+        .map(|v| io::parse_token(lines[1], v).map_err(|err| err.below(1)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let operation = field(&lines, 2, "Operation: new = old ")?;
+    let operation = operation
+        .parse::<Operation>()
+        .map_err(|err| err.at_token(lines[2], operation).below(2))?;
+
+    Ok(Monkey {
+        items,
+        operation,
+        test_value: number(&lines, 3, "Test: divisible by ")?,
+        on_true_monkey: number(&lines, 4, "If true: throw to monkey ")?,
+        on_false_monkey: number(&lines, 5, "If false: throw to monkey ")?,
+        inpections: 0,
+    })
+}
 
-    monkeys
+fn parse_input(input: &str) -> Result<Vec<Monkey>, ParseError> {
+    io::parse_chunks(input, parse_monkey)
 }
 
 fn round<F: Fn(u64) -> u64>(worry_level: F, monkeys: &mut [Monkey]) {
@@ -114,7 +105,7 @@ fn round<F: Fn(u64) -> u64>(worry_level: F, monkeys: &mut [Monkey]) {
 
             let item = worry_level(item);
 
-            let target_monkey = if item % m.test_value == 0 {
+            let target_monkey = if item.is_multiple_of(m.test_value) {
                 m.on_true_monkey
             } else {
                 m.on_false_monkey
@@ -125,8 +116,8 @@ fn round<F: Fn(u64) -> u64>(worry_level: F, monkeys: &mut [Monkey]) {
     }
 }
 
-fn part01(path: &str) -> usize {
-    let mut monkeys = parse_input(path);
+pub fn part01(monkeys: &[Monkey]) -> usize {
+    let mut monkeys = monkeys.to_vec();
 
     for _ in 0..20 {
         round(|w| w / 3, &mut monkeys);
@@ -139,8 +130,8 @@ fn part01(path: &str) -> usize {
     inpections.iter().take(2).product()
 }
 
-fn part02(path: &str) -> usize {
-    let mut monkeys = parse_input(path);
+pub fn part02(monkeys: &[Monkey]) -> usize {
+    let mut monkeys = monkeys.to_vec();
 
     let prod: u64 = monkeys.iter().map(|m| m.test_value).product();
 
@@ -157,27 +148,40 @@ fn part02(path: &str) -> usize {
     inpections.iter().take(2).product()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day11;
 
-    #[test]
-    fn part01_example() {
-        assert_eq!(part01("data/y2022/day11-example.txt"), 10_605);
+impl Solution for Day11 {
+    type Input = Vec<Monkey>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn part01_input() {
-        assert_eq!(part01("data/y2022/day11.txt"), 99_840);
+    fn part1(input: &Self::Input) -> Answer {
+        part01(input).into()
     }
 
-    #[test]
-    fn part02_example() {
-        assert_eq!(part02("data/y2022/day11-example.txt"), 2_713_310_158);
+    fn part2(input: &Self::Input) -> Answer {
+        part02(input).into()
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
-    fn part02_input() {
-        assert_eq!(part02("data/y2022/day11.txt"), 20_683_044_837);
+    fn parse_error() {
+        let input = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisble by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+";
+        let err = parse_input(input).unwrap_err();
+
+        assert_eq!((err.line, err.column), (4, 3));
+        assert_eq!(err.expected, "\"Test: divisible by \"");
     }
 }
@@ -1,108 +1,133 @@
-use std::collections::HashMap;
-
-use crate::grid::Position2D;
+use crate::error::ParseError;
+use crate::grid::{Grid, Position2D};
+use crate::io;
 use crate::search;
+use crate::solution::{Answer, Solution};
+
+pub struct Heightmap {
+    start: Position2D,
+    end: Position2D,
+    heights: Grid<i32>,
+}
+
+struct Height(i32);
 
-fn parse_input(path: &str) -> (Position2D, Position2D, HashMap<Position2D, i32>) {
-    let lines = std::fs::read_to_string(path)
-        .expect("expected file")
-        .lines()
-        .map(|line| line.bytes().collect::<Vec<_>>())
-        .collect::<Vec<_>>();
-
-    let mut m = HashMap::new();
-    let mut start = None;
-    let mut end = None;
-
-    for (y, line) in lines.into_iter().enumerate() {
-        for (x, height) in line.into_iter().enumerate() {
-            let pos = Position2D {
-                x: x.try_into().unwrap(),
-                y: y.try_into().unwrap(),
-            };
-
-            let value = match height {
-                b'E' => {
-                    end = Some(pos);
-                    25
-                }
-                b'S' => {
-                    start = Some(pos);
-                    0
-                }
-                v => (v - b'a') as i32,
-            };
-
-            m.entry(pos).or_insert(value);
+impl TryFrom<char> for Height {
+    type Error = ParseError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'a'..='z' => Ok(Self(value as i32 - 'a' as i32)),
+            _ => Err(ParseError::new("a height (a-z, S or E)")),
         }
     }
+}
+
+fn parse_input(input: &str) -> Result<Heightmap, ParseError> {
+    // the start is at the lowest elevation and the end at the highest
+    let (heights, markers) =
+        io::parse_marked_grid(input, &['S', 'E'], |marker, _| match marker {
+            'S' => 'a',
+            _ => 'z',
+        })?;
+    let marker = |c| {
+        markers
+            .get(&c)
+            .copied()
+            .ok_or_else(|| ParseError::new("a start (S) and end (E) marker"))
+    };
 
-    (start.unwrap(), end.unwrap(), m)
+    Ok(Heightmap {
+        start: marker('S')?,
+        end: marker('E')?,
+        heights: heights.map(|Height(h)| *h),
+    })
 }
 
-fn part01(path: &str) -> usize {
-    let (start, end, m) = parse_input(path);
+// the squares one step up from p at most
+fn climb(heights: &Grid<i32>, p: Position2D) -> Vec<Position2D> {
+    heights.neighbours4(p).filter(|&c| heights[c] - heights[p] <= 1).collect()
+}
 
-    let neighbours = |p: Position2D| {
-        let from = *m.get(&p).unwrap();
-
-        p.neighbours4()
-            .into_iter()
-            .filter(|&c| match m.get(&c) {
-                Some(&to) => to - from <= 1,
-                None => false,
-            })
-            .collect::<Vec<_>>()
-    };
+pub fn part01(map: &Heightmap) -> Option<usize> {
+    let Heightmap { start, end, heights } = map;
 
-    search::shortest_path(start, |p| p == end, neighbours).unwrap()
+    let neighbours = |p| climb(heights, p).into_iter().map(|c| (c, 1));
+
+    // every step moves one square closer at best
+    let heuristic = |p: Position2D| p.distance(end);
+
+    search::astar(*start, |p| p == *end, neighbours, heuristic).0
 }
 
-fn part02(path: &str) -> usize {
-    let (_, end, m) = parse_input(path);
+pub fn part02(map: &Heightmap) -> Option<usize> {
+    let Heightmap { end, heights, .. } = map;
 
+    // walking down from the end, to the squares that can climb up to p
     let neighbours = |p: Position2D| {
-        let from = *m.get(&p).unwrap();
-
-        p.neighbours4()
-            .into_iter()
-            .filter(|&c| match m.get(&c) {
-                Some(&to) => from - to <= 1,
-                None => false,
-            })
-            .collect::<Vec<_>>()
+        heights
+            .neighbours4(p)
+            .filter(move |&c| climb(heights, c).contains(&p))
+            .map(|c| (c, 1))
     };
 
-    let goal = |p| {
-        let height = *m.get(&p).unwrap();
+    // every step goes down one height at most
+    let heuristic = |p| heights[p] as usize;
 
-        height == 0
-    };
+    search::astar(*end, |p| heights[p] == 0, neighbours, heuristic).0
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Heightmap;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        part01(input).map_or(Answer::Unsolved, Answer::from)
+    }
 
-    search::shortest_path(end, goal, neighbours).unwrap()
+    fn part2(input: &Self::Input) -> Answer {
+        part02(input).map_or(Answer::Unsolved, Answer::from)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::render::Render;
 
-    #[test]
-    fn part01_example() {
-        assert_eq!(part01("data/y2022/day12-example.txt"), 31);
-    }
-
-    #[test]
-    fn part01_input() {
-        assert_eq!(part01("data/y2022/day12.txt"), 447);
-    }
+    const EXAMPLE: &str = "Sabqponm\nabcryxxl\naccszExk\nacctuvwj\nabdefghi\n";
 
     #[test]
-    fn part02_example() {
-        assert_eq!(part02("data/y2022/day12-example.txt"), 29);
+    fn routes_up_the_hill() {
+        let map = parse_input(EXAMPLE).unwrap();
+        let paths = search::shortest_paths(
+            map.start,
+            |p| p == map.end,
+            |p| climb(&map.heights, p),
+        );
+
+        assert_eq!(paths.cost(), Some(31));
+        assert_eq!(paths.count(), Some(7));
+
+        let route = Render::grid(&map.heights, |_| '.')
+            .overlay(paths.path().unwrap(), '#')
+            .overlay([map.start], 'S')
+            .overlay([map.end], 'E');
+
+        assert_eq!(
+            route.to_string(),
+            "S#######\n..######\n..###E##\n..######\n..######\n"
+        );
     }
 
     #[test]
-    fn part02_input() {
-        assert_eq!(part02("data/y2022/day12.txt"), 446);
+    fn markers_are_required() {
+        assert!(parse_input("Sabq\nabcz\n").is_err());
+        assert!(parse_input("aabq\nabcE\n").is_err());
     }
 }
@@ -1,38 +1,42 @@
+use crate::error::ParseError;
 use std::str::FromStr;
 
 use crate::io;
+use crate::solution::{Answer, Solution};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
-enum Packet {
+pub enum Packet {
     Integer(usize),
     List(Vec<Packet>),
 }
 
 impl PartialOrd for Packet {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        match (self, other) {
-            (Self::Integer(x), Self::Integer(y)) => x.partial_cmp(y),
-            (Self::List(x), Self::List(y)) => x.partial_cmp(y),
-            (Self::Integer(_), Self::List(_)) => Self::List(vec![self.clone()]).partial_cmp(other),
-            (Self::List(_), Self::Integer(_)) => self.partial_cmp(&Self::List(vec![other.clone()])),
-        }
+        Some(self.cmp(other))
     }
 }
 
 impl Ord for Packet {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.partial_cmp(other).unwrap()
+        match (self, other) {
+            (Self::Integer(x), Self::Integer(y)) => x.cmp(y),
+            (Self::List(x), Self::List(y)) => x.cmp(y),
+            (Self::Integer(_), Self::List(_)) => Self::List(vec![self.clone()]).cmp(other),
+            (Self::List(_), Self::Integer(_)) => self.cmp(&Self::List(vec![other.clone()])),
+        }
     }
 }
 
 impl FromStr for Packet {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let p = if s.starts_with('[') {
-            let s = &s[1..(s.len() - 1)];
+        let p = if let Some(inner) = s.strip_prefix('[') {
+            let inner = inner
+                .strip_suffix(']')
+                .ok_or_else(|| ParseError::new("]").at_column(s.chars().count() + 1))?;
 
-            let mut items = s
+            let mut items = inner
                 .char_indices()
                 .fold((0, vec![0]), |(depth, mut acc), (i, c)| match (depth, c) {
                     (_, '[') => (depth + 1, acc),
@@ -44,27 +48,36 @@ impl FromStr for Packet {
                     _ => (depth, acc),
                 });
 
-            items.1.extend(vec![s.len() + 1]);
+            items.1.extend(vec![inner.len() + 1]);
 
             Self::List(
                 items
                     .1
                     .windows(2)
-                    .filter_map(|idx| s[idx[0]..idx[1] - 1].parse().ok())
-                    .collect::<Vec<_>>(),
+                    .map(|idx| {
+                        let item = &inner[idx[0]..idx[1] - 1];
+
+                        item.parse()
+                            .map_err(|err: ParseError| err.at_token(s, item))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?,
             )
         } else if s.is_empty() {
             Self::List(vec![])
         } else {
-            Self::Integer(s.parse().unwrap())
+            Self::Integer(s.parse()?)
         };
 
         Ok(p)
     }
 }
 
-fn part01(path: &str) -> usize {
-    io::read_value_chunks::<Packet>(path)
+fn parse_input(input: &str) -> Result<Vec<Vec<Packet>>, ParseError> {
+    io::parse_value_chunks(input)
+}
+
+pub fn part01(pairs: &[Vec<Packet>]) -> usize {
+    pairs
         .iter()
         .enumerate()
         .filter_map(|(i, pair)| {
@@ -77,11 +90,8 @@ fn part01(path: &str) -> usize {
         .sum()
 }
 
-fn part02(path: &str) -> usize {
-    let mut packets = io::read_value_chunks::<Packet>(path)
-        .into_iter()
-        .flatten()
-        .collect::<Vec<_>>();
+pub fn part02(pairs: &[Vec<Packet>]) -> usize {
+    let mut packets = pairs.iter().flatten().cloned().collect::<Vec<_>>();
 
     let divider_1 = "[[2]]".parse::<Packet>().unwrap();
     let divider_2 = "[[6]]".parse::<Packet>().unwrap();
@@ -103,6 +113,24 @@ fn part02(path: &str) -> usize {
         .product()
 }
 
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Vec<Vec<Packet>>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        part01(input).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        part02(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -116,22 +144,10 @@ mod tests {
     }
 
     #[test]
-    fn part01_example() {
-        assert_eq!(part01("data/y2022/day13-example.txt"), 13);
-    }
-
-    #[test]
-    fn part01_input() {
-        assert_eq!(part01("data/y2022/day13.txt"), 5393);
-    }
-
-    #[test]
-    fn part02_example() {
-        assert_eq!(part02("data/y2022/day13-example.txt"), 140);
-    }
+    fn parse_error() {
+        let err = parse_input("[1,[2,x]]\n[1]\n").unwrap_err();
 
-    #[test]
-    fn part02_input() {
-        assert_eq!(part02("data/y2022/day13.txt"), 26712);
+        assert_eq!((err.line, err.column), (1, 7));
+        assert_eq!(err.expected, "a number");
     }
 }
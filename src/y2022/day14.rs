@@ -1,58 +1,29 @@
-use std::collections::HashMap;
-use std::hash::Hash;
-
-#[derive(PartialEq)]
-enum Particle {
-    Rock,
-    Sand,
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Position2D {
-    x: i32,
-    y: i32,
-}
-
-impl Position2D {
-    fn new(x: i32, y: i32) -> Self {
-        Self { x, y }
-    }
-
-    fn down(&self) -> Self {
-        Self {
-            x: self.x,
-            y: self.y + 1,
-        }
-    }
-
-    fn down_left(&self) -> Self {
-        Self {
-            x: self.x - 1,
-            y: self.y + 1,
-        }
-    }
-
-    fn down_right(&self) -> Self {
-        Self {
-            x: self.x + 1,
-            y: self.y + 1,
-        }
-    }
-}
-
-fn parse_input(path: &str) -> HashMap<Position2D, Particle> {
-    std::fs::read_to_string(path)
-        .expect("expected file")
-        .lines()
-        .flat_map(|line| {
-            line.split(" -> ")
-                .map(|p| {
-                    let (x, y) = p.split_once(',').unwrap();
-
-                    (x.parse::<i32>().unwrap(), y.parse::<i32>().unwrap())
-                })
-                .collect::<Vec<_>>()
-                .windows(2)
+use crate::bitgrid::BitGrid;
+use crate::error::ParseError;
+use crate::grid::{Direction, Position2D};
+use crate::io;
+use crate::solution::{Answer, Solution};
+
+fn parse_input(input: &str) -> Result<BitGrid, ParseError> {
+    let paths = io::parse_lines(input, |line| {
+        line.split(" -> ")
+            .map(|p| {
+                let (x, y) = p.split_once(',').ok_or_else(|| {
+                    ParseError::new("a point like 498,4").at_token(line, p)
+                })?;
+
+                Ok((
+                    io::parse_token::<i32>(line, x)?,
+                    io::parse_token::<i32>(line, y)?,
+                ))
+            })
+            .collect::<Result<Vec<_>, ParseError>>()
+    })?;
+
+    Ok(paths
+        .iter()
+        .flat_map(|path| {
+            path.windows(2)
                 .flat_map(|el| {
                     let (sx, sy) = el[0];
                     let (ex, ey) = el[1];
@@ -61,38 +32,38 @@ fn parse_input(path: &str) -> HashMap<Position2D, Particle> {
                         let (a, b) = if sy > ey { (ey, sy) } else { (sy, ey) };
 
                         (a..=b)
-                            .map(|y| (Position2D::new(sx, y), Particle::Rock))
+                            .map(|y| Position2D::new(sx, y))
                             .collect::<Vec<_>>()
                     } else {
                         let (a, b) = if sx > ex { (ex, sx) } else { (sx, ex) };
 
                         (a..=b)
-                            .map(|x| (Position2D::new(x, sy), Particle::Rock))
+                            .map(|x| Position2D::new(x, sy))
                             .collect::<Vec<_>>()
                     }
                 })
                 .collect::<Vec<_>>()
         })
-        .collect::<HashMap<_, _>>()
+        .collect())
 }
 
 fn simulate(
-    grid: &HashMap<Position2D, Particle>,
+    grid: &BitGrid,
     void: i32,
     start: &Position2D,
 ) -> Option<Position2D> {
     let mut particle = *start;
 
-    if grid.contains_key(start) {
+    if grid.contains(*start) {
         return None;
     }
 
     'simulation: while particle.y < void {
-        let directions = vec![particle.down(), particle.down_left(), particle.down_right()];
-
-        for direction in directions {
-            if !grid.contains_key(&direction) {
-                particle = direction;
+        for direction in
+            [Direction::Down, Direction::DownLeft, Direction::DownRight]
+        {
+            if !grid.contains(particle + direction) {
+                particle += direction;
                 continue 'simulation;
             }
         }
@@ -103,72 +74,55 @@ fn simulate(
     None
 }
 
-fn fill(
-    mut grid: HashMap<Position2D, Particle>,
-    void: i32,
-    start: &Position2D,
-) -> HashMap<Position2D, Particle> {
+fn fill(mut grid: BitGrid, void: i32, start: &Position2D) -> BitGrid {
     while let Some(particule) = simulate(&grid, void, start) {
-        grid.insert(particule, Particle::Sand);
+        grid.insert(particule);
     }
 
     grid
 }
 
-fn part01(path: &str) -> usize {
-    let grid = parse_input(path);
-    let void = grid.keys().map(|p| p.y).max().unwrap();
+pub fn part01(grid: &BitGrid) -> usize {
+    let void = grid.iter().map(|p| p.y).max().unwrap();
     let start = Position2D::new(500, 0);
-    let after = fill(grid, void, &start);
+    let after = fill(grid.clone(), void, &start);
 
-    after
-        .values()
-        .filter(|v| matches!(v, Particle::Sand))
-        .count()
+    after.len() - grid.len()
 }
 
-fn part02(path: &str) -> usize {
-    let mut grid = parse_input(path);
-    let bottom = 2 + grid.keys().map(|p| p.y).max().unwrap();
-    let xs = grid.keys().map(|p| p.x).collect::<Vec<_>>();
+pub fn part02(grid: &BitGrid) -> usize {
+    let mut grid = grid.clone();
+    let bottom = 2 + grid.iter().map(|p| p.y).max().unwrap();
+    let xs = grid.iter().map(|p| p.x).collect::<Vec<_>>();
     let min_x = *xs.iter().min().unwrap();
     let max_x = *xs.iter().max().unwrap();
 
     grid.extend(
-        (min_x - bottom..max_x + bottom).map(|x: i32| (Position2D::new(x, bottom), Particle::Rock)),
+        (min_x - bottom..max_x + bottom)
+            .map(|x: i32| Position2D::new(x, bottom)),
     );
 
     let start = Position2D::new(500, 0);
 
-    let after = fill(grid, bottom, &start);
+    let after = fill(grid.clone(), bottom, &start);
 
-    after
-        .values()
-        .filter(|v| matches!(v, Particle::Sand))
-        .count()
+    after.len() - grid.len()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day14;
 
-    #[test]
-    fn part01_example() {
-        assert_eq!(part01("data/y2022/day14-example.txt"), 24);
-    }
+impl Solution for Day14 {
+    type Input = BitGrid;
 
-    #[test]
-    fn part01_input() {
-        assert_eq!(part01("data/y2022/day14.txt"), 1513);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn part02_example() {
-        assert_eq!(part02("data/y2022/day14-example.txt"), 93);
+    fn part1(input: &Self::Input) -> Answer {
+        part01(input).into()
     }
 
-    #[test]
-    fn part02_input() {
-        assert_eq!(part02("data/y2022/day14.txt"), 22646);
+    fn part2(input: &Self::Input) -> Answer {
+        part02(input).into()
     }
 }
@@ -1,52 +1,58 @@
-use crate::grid::Position2D;
+use crate::error::ParseError;
 use crate::io;
+use crate::point::Point2;
+use crate::solution::{Answer, Params, Solution};
 use std::collections::HashSet;
 use std::str::FromStr;
 
+type Position = Point2<i64>;
+
 #[derive(Debug, Eq, PartialEq, Hash)]
-struct Segment {
-    sensor: Position2D,
-    beacon: Position2D,
+pub struct Segment {
+    sensor: Position,
+    beacon: Position,
 }
 
 impl Segment {
-    fn scan_range(&self) -> i32 {
-        self.sensor.distance(&self.beacon) as _
+    fn scan_range(&self) -> i64 {
+        self.sensor.manhattan(&self.beacon)
     }
 }
 
+fn position(line: &str, s: &str) -> Result<Position, ParseError> {
+    let (x, y) = s
+        .strip_prefix("x=")
+        .and_then(|rest| rest.split_once(", y="))
+        .ok_or_else(|| ParseError::new("x=<x>, y=<y>").at_token(line, s))?;
+
+    Ok(Position::new(io::parse_token(line, x)?, io::parse_token(line, y)?))
+}
+
 impl FromStr for Segment {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut sep = s
-            .char_indices()
-            .filter_map(|(i, c)| match c {
-                '=' => Some(i + 1),
-                ':' | ',' => Some(i),
-                _ => None,
-            })
-            .collect::<Vec<usize>>();
-
-        sep.push(s.len());
-
-        let pos = sep
-            .chunks(2)
-            .filter_map(|idx| s[idx[0]..idx[1]].parse::<i32>().ok())
-            .collect::<Vec<_>>();
+        let (sensor, beacon) = s
+            .strip_prefix("Sensor at ")
+            .and_then(|rest| rest.split_once(": closest beacon is at "))
+            .ok_or_else(|| {
+                ParseError::new(
+                    "Sensor at <position>: closest beacon is at <position>",
+                )
+            })?;
 
         Ok(Segment {
-            sensor: Position2D::new(pos[0], pos[1]),
-            beacon: Position2D::new(pos[2], pos[3]),
+            sensor: position(s, sensor)?,
+            beacon: position(s, beacon)?,
         })
     }
 }
 
 #[derive(Debug)]
-struct Interval(i32, i32);
+struct Interval(i64, i64);
 
 impl Interval {
-    fn len(&self) -> i32 {
+    fn len(&self) -> i64 {
         self.1 - self.0 + 1
     }
 
@@ -63,7 +69,7 @@ impl Interval {
     }
 }
 
-fn scan(segments: &[Segment], row: i32) -> Vec<Interval> {
+fn scan(segments: &[Segment], row: i64) -> Vec<Interval> {
     segments.iter().fold(Vec::new(), |acc, s| {
         let range = s.scan_range() - (s.sensor.y - row).abs();
 
@@ -82,59 +88,84 @@ fn scan(segments: &[Segment], row: i32) -> Vec<Interval> {
     })
 }
 
-fn part01(path: &str, y: i32) -> i32 {
-    let segments = io::read_value_per_line::<Segment>(path);
-    let intervals: i32 = scan(&segments, y).iter().map(|i| i.len()).sum();
+// the sensors and what the puzzle asks about them, which it states in its
+// text: the row for part 1, and the largest coordinate the distress beacon
+// can have for part 2. both are given as params for the examples
+pub struct Scan {
+    segments: Vec<Segment>,
+    row: i64,
+    bound: i64,
+}
+
+fn parse_input(input: &str, params: &Params) -> Result<Scan, ParseError> {
+    Ok(Scan {
+        segments: io::parse_value_per_line(input)?,
+        row: params.get("row").unwrap_or(2_000_000),
+        bound: params.get("bound").unwrap_or(4_000_000),
+    })
+}
+
+pub fn part01(segments: &[Segment], y: i64) -> i64 {
+    let intervals: i64 = scan(segments, y).iter().map(|i| i.len()).sum();
     let beacons = segments
         .iter()
-        .filter_map(|s| {
-            if s.beacon.y == y {
-                Some(s.beacon.x)
-            } else {
-                None
-            }
-        })
+        .filter_map(|s| if s.beacon.y == y { Some(s.beacon.x) } else { None })
         .collect::<HashSet<_>>()
         .len();
 
-    intervals - beacons as i32
+    intervals - beacons as i64
 }
 
-fn part02(path: &str) -> i64 {
-    let segments = io::read_value_per_line::<Segment>(path);
-
-    for y in 0..=4000000 {
-        let intervals = scan(&segments, y);
+// the first column from 0 on that none of the intervals, sorted by their
+// start, covers
+fn first_uncovered(intervals: &[Interval]) -> i64 {
+    let mut x = 0;
 
-        if intervals.len() > 1 {
-            return (intervals[0].1.min(intervals[1].1) as i64 + 1) * 4000000 + y as i64;
+    for interval in intervals {
+        if interval.0 > x {
+            break;
         }
+        x = x.max(interval.1 + 1);
     }
 
-    unreachable!()
+    x
+}
+
+// the tuning frequency of the only position between 0 and `bound` on both
+// axes that no sensor covers
+pub fn part02(segments: &[Segment], bound: i64) -> Option<i64> {
+    (0..=bound).find_map(|y| {
+        let mut intervals = scan(segments, y);
+        intervals.sort_by_key(|i| i.0);
+
+        let x = first_uncovered(&intervals);
+
+        (x <= bound).then_some(x * 4_000_000 + y)
+    })
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = Scan;
 
-    #[test]
-    fn part01_example() {
-        assert_eq!(part01("data/y2022/day15-example.txt", 10), 26);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input, &Params::default())
     }
 
-    #[test]
-    fn part01_input() {
-        assert_eq!(part01("data/y2022/day15.txt", 2_000_000), 5_525_847);
+    fn parse_with(
+        input: &str,
+        params: &Params,
+    ) -> Result<Self::Input, ParseError> {
+        parse_input(input, params)
     }
 
-    #[test]
-    fn part02_example() {
-        assert_eq!(part02("data/y2022/day15-example.txt"), 56000011);
+    fn part1(input: &Self::Input) -> Answer {
+        part01(&input.segments, input.row).into()
     }
 
-    #[test]
-    fn part02_input() {
-        assert_eq!(part02("data/y2022/day15.txt"), 13340867187704);
+    fn part2(input: &Self::Input) -> Answer {
+        part02(&input.segments, input.bound)
+            .map_or(Answer::Unsolved, Answer::from)
     }
 }
@@ -1,47 +1,65 @@
+use crate::error::ParseError;
+use crate::io;
 use crate::search::shortest_path;
+use crate::solution::{Answer, Solution};
 use itertools::Itertools;
 use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
 
 #[derive(Debug)]
-struct Room<'a>(&'a str, usize, Vec<&'a str>);
-
-fn parse_input(input: &str) -> HashMap<&str, Room> {
-    input
-        .lines()
-        .map(|line| {
-            let fields = line.split([' ', '=', ',', ';']).collect::<Vec<_>>();
-
-            let name = fields[1];
-            let rate = fields[5].parse().unwrap();
-            let tunnels = fields[11..]
-                .iter()
-                .filter_map(|s| if !s.is_empty() { Some(*s) } else { None })
-                .collect::<Vec<_>>();
-
-            (name, Room(name, rate, tunnels))
-        })
-        .collect()
+pub struct Room(String, usize, Vec<String>);
+
+fn parse_input(input: &str) -> Result<HashMap<String, Room>, ParseError> {
+    let rooms = io::parse_lines(input, |line| {
+        let (name, rest) = line
+            .strip_prefix("Valve ")
+            .and_then(|rest| rest.split_once(" has flow rate="))
+            .ok_or_else(|| ParseError::new("Valve <name> has flow rate=<rate>"))?;
+
+        let (rate, tunnels) = rest
+            .split_once("; ")
+            .ok_or_else(|| ParseError::new("; after the flow rate").at_token(line, rest))?;
+
+        let tunnels = tunnels
+            .strip_prefix("tunnels lead to valves ")
+            .or_else(|| tunnels.strip_prefix("tunnel leads to valve "))
+            .ok_or_else(|| ParseError::new("a list of tunnels").at_token(line, tunnels))?
+            .split(", ")
+            .map(|s| s.to_string())
+            .collect::<Vec<_>>();
+
+        let rate = io::parse_token(line, rate)?;
+
+        Ok((name.to_string(), Room(name.to_string(), rate, tunnels)))
+    })?;
+
+    Ok(rooms.into_iter().collect())
 }
 
-fn shortest_paths<'a>(rooms: &'a HashMap<&str, Room>) -> HashMap<(&'a str, &'a str), usize> {
-    let neighbours = |room| {
-        let from = &rooms.get(&room).unwrap();
+fn shortest_paths(rooms: &HashMap<String, Room>) -> HashMap<(&str, &str), usize> {
+    let neighbours = |room: &str| {
+        let from = &rooms[room];
 
-        from.2.clone()
+        from.2.iter().map(String::as_str).collect::<Vec<_>>()
     };
 
     rooms
         .iter()
-        .filter_map(|(name, room)| if room.1 > 0 { Some(name) } else { None })
+        .filter_map(|(name, room)| {
+            if room.1 > 0 {
+                Some(name.as_str())
+            } else {
+                None
+            }
+        })
         .tuple_combinations()
         .fold(HashMap::new(), |mut acc, (name1, name2)| {
             acc.entry(("AA", name1))
-                .or_insert_with(|| shortest_path("AA", |el| el == *name1, neighbours).unwrap());
+                .or_insert_with(|| shortest_path("AA", |el| el == name1, neighbours).unwrap());
 
             acc.entry(("AA", name2))
-                .or_insert_with(|| shortest_path("AA", |el| el == *name2, neighbours).unwrap());
+                .or_insert_with(|| shortest_path("AA", |el| el == name2, neighbours).unwrap());
 
-            let dist = shortest_path(*name1, |el| el == *name2, neighbours).unwrap();
+            let dist = shortest_path(name1, |el| el == name2, neighbours).unwrap();
 
             acc.insert((name1, name2), dist);
             acc.insert((name2, name1), dist);
@@ -63,17 +81,17 @@ fn wait_until_ending(
     elapsed: usize,
     relieved: usize,
     opened: &BTreeSet<&str>,
-    map: &HashMap<&str, Room>,
+    map: &HashMap<String, Room>,
 ) -> usize {
     let time_left = max_time - elapsed;
-    let relieved_per_min: usize = opened.iter().map(|name| &map[name].1).sum();
+    let relieved_per_min: usize = opened.iter().map(|name| &map[*name].1).sum();
 
     relieved + (relieved_per_min * time_left)
 }
 
 fn search_caves<'a>(
     flowing_caves: &HashSet<&'a str>,
-    caves: &HashMap<&str, Room>,
+    caves: &HashMap<String, Room>,
     distances: &HashMap<(&str, &str), usize>,
     mut seen: HashSet<(BTreeSet<&'a str>, usize, usize)>,
     time_allowed: usize,
@@ -119,7 +137,7 @@ fn search_caves<'a>(
                 continue;
             }
 
-            let relieved_per_min: usize = opened.iter().map(|name| &caves[name].1).sum();
+            let relieved_per_min: usize = opened.iter().map(|name| &caves[*name].1).sum();
             let new_relieved = relieved + (relieved_per_min * cost);
             let mut new_opened = opened.clone();
             new_opened.insert(dest);
@@ -137,32 +155,40 @@ fn search_caves<'a>(
     max_relieved_states
 }
 
-fn part01(path: &str) -> usize {
-    let input = std::fs::read_to_string(path).expect("expected file");
-    let caves = parse_input(&input);
-    let distances = shortest_paths(&caves);
+pub fn part01(caves: &HashMap<String, Room>) -> usize {
+    let distances = shortest_paths(caves);
     let flowing_caves = caves
-        .iter()
-        .filter_map(|(_, room)| if room.1 > 0 { Some(room.0) } else { None })
+        .values()
+        .filter_map(|room| {
+            if room.1 > 0 {
+                Some(room.0.as_str())
+            } else {
+                None
+            }
+        })
         .collect::<HashSet<_>>();
 
-    search_caves(&flowing_caves, &caves, &distances, HashSet::new(), 30)
+    search_caves(&flowing_caves, caves, &distances, HashSet::new(), 30)
         .values()
         .copied()
         .max()
         .unwrap_or(0)
 }
 
-fn part02(path: &str) -> usize {
-    let input = std::fs::read_to_string(path).expect("expected file");
-    let caves = parse_input(&input);
-    let distances = shortest_paths(&caves);
+pub fn part02(caves: &HashMap<String, Room>) -> usize {
+    let distances = shortest_paths(caves);
     let flowing_caves = caves
-        .iter()
-        .filter_map(|(_, room)| if room.1 > 0 { Some(room.0) } else { None })
+        .values()
+        .filter_map(|room| {
+            if room.1 > 0 {
+                Some(room.0.as_str())
+            } else {
+                None
+            }
+        })
         .collect::<HashSet<_>>();
 
-    search_caves(&flowing_caves, &caves, &distances, HashSet::new(), 26)
+    search_caves(&flowing_caves, caves, &distances, HashSet::new(), 26)
         .iter()
         .tuple_combinations()
         .filter_map(|(human, elephant)| {
@@ -176,27 +202,20 @@ fn part02(path: &str) -> usize {
         .unwrap_or(0)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day16;
 
-    #[test]
-    fn part01_example() {
-        assert_eq!(part01("data/y2022/day16-example.txt"), 1651);
-    }
+impl Solution for Day16 {
+    type Input = HashMap<String, Room>;
 
-    #[test]
-    fn part01_input() {
-        assert_eq!(part01("data/y2022/day16.txt"), 1751);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn part02_example() {
-        assert_eq!(part02("data/y2022/day16-example.txt"), 1707);
+    fn part1(input: &Self::Input) -> Answer {
+        part01(input).into()
     }
 
-    #[test]
-    fn part02_input() {
-        assert_eq!(part02("data/y2022/day16.txt"), 2207);
+    fn part2(input: &Self::Input) -> Answer {
+        part02(input).into()
     }
 }
@@ -1,174 +1,127 @@
-use std::collections::{HashMap, HashSet};
-
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-struct Pos(i64, i64);
-
-impl std::ops::Add for Pos {
-    type Output = Pos;
-
-    fn add(self, other: Pos) -> Pos {
-        Pos(self.0 + other.0, self.1 + other.1)
-    }
-}
-
-impl std::ops::AddAssign for Pos {
-    fn add_assign(&mut self, other: Pos) {
-        *self = Pos(self.0 + other.0, self.1 + other.1)
-    }
-}
-
-impl std::ops::Sub for Pos {
-    type Output = Pos;
-
-    fn sub(self, other: Pos) -> Pos {
-        Pos(self.0 - other.0, self.1 - other.1)
-    }
-}
-
-const ROCKS: [&[Pos]; 5] = [
-    &[Pos(0, 0), Pos(1, 0), Pos(2, 0), Pos(3, 0)],
-    &[Pos(1, 0), Pos(0, 1), Pos(1, 1), Pos(2, 1), Pos(1, 2)],
-    &[Pos(0, 0), Pos(1, 0), Pos(2, 0), Pos(2, 1), Pos(2, 2)],
-    &[Pos(0, 0), Pos(0, 1), Pos(0, 2), Pos(0, 3)],
-    &[Pos(0, 0), Pos(1, 0), Pos(0, 1), Pos(1, 1)],
+use crate::bitgrid::BitGrid;
+use crate::error::ParseError;
+use crate::grid::{Direction, Position2D};
+use crate::render::Render;
+use crate::solution::{Answer, Solution};
+use std::collections::HashMap;
+use std::fmt;
+
+// each rock a row at a time from its bottom, bit 0 being its left edge
+const ROCKS: [&[u64]; 5] = [
+    &[0b1111],
+    &[0b010, 0b111, 0b010],
+    &[0b111, 0b100, 0b100],
+    &[0b1, 0b1, 0b1, 0b1],
+    &[0b11, 0b11],
 ];
 
-const DOWN: Pos = Pos(0, -1);
+const WIDTH: i32 = 7;
 
-fn parse_input(path: &str) -> Vec<Pos> {
-    std::fs::read_to_string(path)
-        .expect("expected file")
+fn parse_input(input: &str) -> Result<Vec<Direction>, ParseError> {
+    input
         .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| match c {
-                    '<' => Pos(-1, 0),
-                    '>' => Pos(1, 0),
-                    _ => unreachable!(),
-                })
-                .collect::<Vec<_>>()
-        })
         .next()
-        .unwrap()
-}
-
-fn maybe_move(rocks: &[Pos], offset: &Pos) -> Option<Vec<Pos>> {
-    let rocks = rocks.iter().map(|p| *p + *offset).collect::<Vec<_>>();
-
-    if rocks.iter().any(|p| p.0 < 0 || p.0 > 6) {
-        None
-    } else {
-        Some(rocks)
-    }
+        .ok_or_else(|| ParseError::new("a jet pattern"))?
+        .chars()
+        .enumerate()
+        .map(|(x, c)| match c {
+            '<' => Ok(Direction::Left),
+            '>' => Ok(Direction::Right),
+            _ => Err(ParseError::new("a jet (< or >)").at_column(x + 1)),
+        })
+        .collect()
 }
 
 struct Chamber {
-    rocks: HashSet<Pos>,
-    jet_patterns: Vec<Pos>,
+    rocks: BitGrid,
+    jet_patterns: Vec<Direction>,
     piece_count: usize,
     jet_count: usize,
     height: i64,
 }
 
 impl Chamber {
-    fn new(jet_patterns: Vec<Pos>) -> Self {
-        Self {
-            rocks: HashSet::from_iter((0..=6).map(|x| Pos(x, -1))),
-            jet_patterns,
-            piece_count: 0,
-            jet_count: 0,
-            height: 0,
-        }
-    }
+    fn new(jet_patterns: Vec<Direction>) -> Self {
+        let mut rocks = BitGrid::new();
+        rocks.insert_word(0, -1, (1 << WIDTH) - 1);
 
-    #[allow(dead_code)]
-    fn print_chamber(&self, rocks: &[Pos]) {
-        let rocks: HashSet<Pos> = HashSet::from_iter(rocks.iter().cloned());
-
-        let chamber_max_y = self.rocks.iter().map(|p| p.1).max().unwrap_or(0);
-        let rocks_max_y = rocks.iter().map(|p| p.1).max().unwrap_or(0);
-
-        let max_y = chamber_max_y.max(rocks_max_y);
-
-        for y in (-1..=max_y).rev() {
-            for x in -1..=7 {
-                let ch = match (x, y) {
-                    (-1, -1) | (7, -1) => '+',
-                    (_, -1) => '-',
-                    (-1, _) | (7, _) => '|',
-                    (_, _) => {
-                        let p = &Pos(x, y);
-                        if self.rocks.contains(p) {
-                            '#'
-                        } else if rocks.contains(p) {
-                            '@'
-                        } else {
-                            '.'
-                        }
-                    }
-                };
-                print!("{}", ch);
-            }
-            println!();
-        }
-        println!();
+        Self { rocks, jet_patterns, piece_count: 0, jet_count: 0, height: 0 }
     }
 
-    fn collides(&self, piece: &[Pos]) -> bool {
-        piece.iter().any(|p| self.rocks.contains(p))
+    // whether the rock, its left edge at x and its bottom at y, overlaps the
+    // walls or anything already at rest
+    fn collides(&self, rock: &[u64], x: i32, y: i32) -> bool {
+        x < 0
+            || rock.iter().enumerate().any(|(dy, row)| {
+                row << x >= 1 << WIDTH
+                    || self.rocks.word(0, y + dy as i32) & row << x != 0
+            })
     }
 
     fn top(&self) -> Vec<i64> {
-        let ys = self.rocks.iter().fold(vec![0; 7], |mut acc, p| {
-            acc[p.0 as usize] = acc[p.0 as usize].max(p.1);
-            acc
-        });
+        let ys = (0..WIDTH)
+            .map(|x| {
+                (-1..self.height as i32)
+                    .rev()
+                    .find(|&y| self.rocks.contains(Position2D::new(x, y)))
+                    .expect("the floor at y = -1 spans every column")
+                    as i64
+            })
+            .collect::<Vec<_>>();
 
-        let min_y = *ys.iter().min().unwrap();
+        let min_y =
+            *ys.iter().min().expect("a chamber at least a column wide");
 
         ys.into_iter().map(|y| y - min_y).collect::<Vec<_>>()
     }
 
     fn drop_piece(&mut self) {
-        let starting_offset = Pos(2, self.height + 3);
-
-        let mut piece = ROCKS[self.piece_count]
-            .iter()
-            .map(|p| *p + starting_offset)
-            .collect::<Vec<_>>();
+        let rock = ROCKS[self.piece_count];
+        let (mut x, mut y) = (2, self.height as i32 + 3);
 
         self.piece_count = (self.piece_count + 1) % ROCKS.len();
 
         loop {
-            let jet = self.jet_patterns[self.jet_count];
+            let jet = self.jet_patterns[self.jet_count].to_offset().x;
             self.jet_count = (self.jet_count + 1) % self.jet_patterns.len();
 
-            if let Some(attempt) = maybe_move(&piece, &jet) {
-                if !self.collides(&attempt) {
-                    piece = attempt;
-                }
-            };
-
-            if let Some(attempt) = maybe_move(&piece, &DOWN) {
-                if !self.collides(&attempt) {
-                    piece = attempt;
-                } else {
-                    break;
-                }
-            };
+            if !self.collides(rock, x + jet, y) {
+                x += jet;
+            }
+
+            if self.collides(rock, x, y - 1) {
+                break;
+            }
+            y -= 1;
         }
 
-        self.rocks.extend(piece.iter());
-        self.height = self
-            .height
-            .max(piece.iter().map(|p| p.1 + 1).max().unwrap());
+        for (dy, row) in rock.iter().enumerate() {
+            self.rocks.insert_word(0, y + dy as i32, row << x);
+        }
+        self.height = self.height.max((y + rock.len() as i32) as i64);
     }
 }
 
-fn part01(path: &str) -> i64 {
-    let jet_patterns = parse_input(path);
+// the walls and floor around the rocks, drawn the way the puzzle does
+impl fmt::Display for Chamber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let top = self.height as i32 - 1;
+        let at = |x, y| Position2D::new(x, y);
+
+        let render = Render::new()
+            .overlay(self.rocks.iter(), '#')
+            .overlay((0..=top).flat_map(|y| [at(-1, y), at(WIDTH, y)]), '|')
+            .overlay((0..WIDTH).map(|x| at(x, -1)), '-')
+            .overlay([at(-1, -1), at(WIDTH, -1)], '+')
+            .viewport(at(-1, -1), at(WIDTH, top))
+            .y_up();
+
+        write!(f, "{render}")
+    }
+}
 
-    let mut chamber = Chamber::new(jet_patterns);
+pub fn part01(jet_patterns: &[Direction]) -> i64 {
+    let mut chamber = Chamber::new(jet_patterns.to_vec());
 
     for _ in 0..2022 {
         chamber.drop_piece();
@@ -177,11 +130,10 @@ fn part01(path: &str) -> i64 {
     chamber.height
 }
 
-fn part02(path: &str) -> i64 {
-    let jet_patterns = parse_input(path);
-
-    let mut chamber = Chamber::new(jet_patterns);
-    let mut seen: HashMap<(Vec<i64>, usize, usize), (i64, i64)> = HashMap::new();
+pub fn part02(jet_patterns: &[Direction]) -> i64 {
+    let mut chamber = Chamber::new(jet_patterns.to_vec());
+    let mut seen: HashMap<(Vec<i64>, usize, usize), (i64, i64)> =
+        HashMap::new();
     let mut drop_count: i64 = 0;
     loop {
         chamber.drop_piece();
@@ -192,9 +144,9 @@ fn part02(path: &str) -> i64 {
 
         if let Some(entry) = seen.get(&state) {
             let height_per_loop = chamber.height - entry.0;
-            let drops_per_loop = drop_count - (entry.1);
+            let drops_per_loop = drop_count - entry.1;
 
-            let remaining_drops = 1000000000000_i64 - (entry.1);
+            let remaining_drops = 1_000_000_000_000 - entry.1;
 
             let loops = remaining_drops / drops_per_loop;
             let remaining_drops = remaining_drops % drops_per_loop;
@@ -203,7 +155,7 @@ fn part02(path: &str) -> i64 {
                 chamber.drop_piece();
             }
 
-            let height_loops = entry.0 + height_per_loop * (loops);
+            let height_loops = entry.0 + height_per_loop * loops;
 
             return height_loops + chamber.height - height;
         } else {
@@ -212,27 +164,47 @@ fn part02(path: &str) -> i64 {
     }
 }
 
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = Vec<Direction>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        part01(input).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        part02(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn part01_example() {
-        assert_eq!(part01("data/y2022/day17-example.txt"), 3068);
-    }
+    fn falling_rocks() {
+        let mut chamber = Chamber::new(
+            parse_input(">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>\n").unwrap(),
+        );
 
-    #[test]
-    fn part01_input() {
-        assert_eq!(part01("data/y2022/day17.txt"), 3059);
-    }
+        chamber.drop_piece();
+        chamber.drop_piece();
 
-    #[test]
-    fn part02_example() {
-        assert_eq!(part02("data/y2022/day17-example.txt"), 1514285714288);
+        assert_eq!(
+            chamber.to_string(),
+            "|...#...|\n|..###..|\n|...#...|\n|..####.|\n+-------+\n"
+        );
     }
 
     #[test]
-    fn part02_input() {
-        assert_eq!(part02("data/y2022/day17.txt"), 1500874635587);
+    fn parse_error() {
+        let err = parse_input("<<>x>\n").unwrap_err();
+
+        assert_eq!((err.line, err.column), (1, 4));
     }
 }
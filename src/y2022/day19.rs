@@ -1,23 +1,28 @@
+use crate::error::ParseError;
+use crate::io;
+use crate::solution::{Answer, Solution};
 use std::collections::VecDeque;
 
-fn parse_input(path: &str) -> Vec<[[u16; 4]; 4]> {
-    std::fs::read_to_string(path)
-        .expect("should be able to read file")
-        .lines()
-        .map(|line| {
-            let digits = line
-                .split_whitespace()
-                .filter_map(|v| v.parse().ok())
-                .collect::<Vec<_>>();
-
-            [
-                [digits[0], 0, 0, 0],
-                [digits[1], 0, 0, 0],
-                [digits[2], digits[3], 0, 0],
-                [digits[4], 0, digits[5], 0],
-            ]
-        })
-        .collect()
+type Blueprint = [[u16; 4]; 4];
+
+fn parse_input(input: &str) -> Result<Vec<Blueprint>, ParseError> {
+    io::parse_lines(input, |line| {
+        let digits = line
+            .split_whitespace()
+            .filter_map(|v| v.parse().ok())
+            .collect::<Vec<_>>();
+
+        let [ore, clay, obsidian_ore, obsidian_clay, geode_ore, geode_obsidian] = digits[..] else {
+            return Err(ParseError::new("a blueprint with six robot costs"));
+        };
+
+        Ok([
+            [ore, 0, 0, 0],
+            [clay, 0, 0, 0],
+            [obsidian_ore, obsidian_clay, 0, 0],
+            [geode_ore, 0, geode_obsidian, 0],
+        ])
+    })
 }
 
 struct State {
@@ -29,7 +34,7 @@ struct State {
     elapsed: u16,
 }
 
-fn max_geodes(blueprint: &[[u16; 4]; 4], max_time: u16) -> u16 {
+fn max_geodes(blueprint: &Blueprint, max_time: u16) -> u16 {
     // calculate the maximum amount for every type of bot so that the creation of a new bot of any type is never bottlenecked
     // it doesn't make sense to build more bots than that maximum if the resources a bot type generates are
     // enough to cover that type (ore, clay, obsidian) cost for any possible bot (per question, you can only build 1 bot per turn)
@@ -72,7 +77,7 @@ fn max_geodes(blueprint: &[[u16; 4]; 4], max_time: u16) -> u16 {
                         // no target bot type made yet
                         // we can't build it (it takes more than max_time to build it).
                         _ if bots[idx] == 0 => max_time + 1,
-                        _ => (costs[idx] - inventory[idx] + bots[idx] - 1) / bots[idx],
+                        _ => (costs[idx] - inventory[idx]).div_ceil(bots[idx]),
                     }
                 })
                 .max()
@@ -120,8 +125,8 @@ fn max_geodes(blueprint: &[[u16; 4]; 4], max_time: u16) -> u16 {
     max_geodes
 }
 
-fn part_01(path: &str) -> usize {
-    parse_input(path)
+pub fn part01(blueprints: &[Blueprint]) -> usize {
+    blueprints
         .iter()
         .map(|blueprint| max_geodes(blueprint, 24))
         .enumerate()
@@ -129,35 +134,28 @@ fn part_01(path: &str) -> usize {
         .sum()
 }
 
-fn part_02(path: &str) -> usize {
-    parse_input(path)
+pub fn part02(blueprints: &[Blueprint]) -> usize {
+    blueprints
         .iter()
         .take(3)
         .map(|blueprint| usize::from(max_geodes(blueprint, 32)))
         .product()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day19;
 
-    #[test]
-    fn part01_example() {
-        assert_eq!(part_01("data/y2022/day19-example.txt"), 33);
-    }
+impl Solution for Day19 {
+    type Input = Vec<Blueprint>;
 
-    #[test]
-    fn part01_input() {
-        assert_eq!(part_01("data/y2022/day19.txt"), 1487);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn part02_example() {
-        assert_eq!(part_02("data/y2022/day19-example.txt"), 3472);
+    fn part1(input: &Self::Input) -> Answer {
+        part01(input).into()
     }
 
-    #[test]
-    fn part02_input() {
-        assert_eq!(part_02("data/y2022/day19.txt"), 13440);
+    fn part2(input: &Self::Input) -> Answer {
+        part02(input).into()
     }
 }
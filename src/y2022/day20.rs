@@ -1,4 +1,6 @@
-use crate::io::read_value_per_line;
+use crate::error::ParseError;
+use crate::io::parse_value_per_line;
+use crate::solution::{Answer, Solution};
 
 #[derive(Debug, Clone)]
 struct Number {
@@ -6,8 +8,8 @@ struct Number {
     value: i64,
 }
 
-fn decrypt(numbers: Vec<Number>) -> Vec<Number> {
-    let mut result = numbers.clone();
+fn decrypt(numbers: &[Number]) -> Vec<Number> {
+    let mut result = numbers.to_vec();
 
     for original_index in 0..numbers.len() {
         let index = result
@@ -25,8 +27,12 @@ fn decrypt(numbers: Vec<Number>) -> Vec<Number> {
     result
 }
 
-fn part01(path: &str) -> i64 {
-    let values = read_value_per_line::<i64>(path)
+fn parse_input(input: &str) -> Result<Vec<i64>, ParseError> {
+    parse_value_per_line(input)
+}
+
+pub fn part01(values: &[i64]) -> i64 {
+    let values = values
         .iter()
         .enumerate()
         .map(|(idx, value)| Number {
@@ -35,7 +41,7 @@ fn part01(path: &str) -> i64 {
         })
         .collect::<Vec<Number>>();
 
-    let decrypted = decrypt(values);
+    let decrypted = decrypt(&values);
 
     let zero = decrypted
         .iter()
@@ -50,8 +56,8 @@ fn part01(path: &str) -> i64 {
 
 const DECRYPTION_KEY: i64 = 811589153;
 
-fn part02(path: &str) -> i64 {
-    let values = read_value_per_line::<i64>(path)
+pub fn part02(values: &[i64]) -> i64 {
+    let values = values
         .iter()
         .enumerate()
         .map(|(idx, value)| Number {
@@ -63,7 +69,7 @@ fn part02(path: &str) -> i64 {
     let mut decrypted = values;
 
     for _ in 0..10 {
-        decrypted = decrypt(decrypted);
+        decrypted = decrypt(&decrypted);
     }
 
     let zero = decrypted
@@ -77,27 +83,20 @@ fn part02(path: &str) -> i64 {
         .sum()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day20;
 
-    #[test]
-    fn part01_example() {
-        assert_eq!(part01("data/y2022/day20-example.txt"), 3);
-    }
+impl Solution for Day20 {
+    type Input = Vec<i64>;
 
-    #[test]
-    fn part01_input() {
-        assert_eq!(part01("data/y2022/day20.txt"), 23321);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn part02_example() {
-        assert_eq!(part02("data/y2022/day20-example.txt"), 1623178306);
+    fn part1(input: &Self::Input) -> Answer {
+        part01(input).into()
     }
 
-    #[test]
-    fn part02_input() {
-        assert_eq!(part02("data/y2022/day20.txt"), 1428396909280);
+    fn part2(input: &Self::Input) -> Answer {
+        part02(input).into()
     }
 }
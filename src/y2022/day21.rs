@@ -1,7 +1,10 @@
+use crate::error::ParseError;
+use crate::io;
+use crate::solution::{Answer, Solution};
 use std::collections::HashMap;
 
 #[derive(Debug)]
-enum Operator {
+pub enum Operator {
     Sub,
     Add,
     Div,
@@ -38,38 +41,44 @@ impl Operator {
 }
 
 #[derive(Debug)]
-enum Monkey<'a> {
+pub enum Monkey {
     Number(i64),
-    Operation(&'a str, Operator, &'a str),
+    Operation(String, Operator, String),
 }
 
-fn parse_input(input: &str) -> HashMap<&str, Monkey> {
-    input
-        .lines()
-        .map(|line| {
-            let fields = line.split_whitespace().collect::<Vec<_>>();
-
-            let name = fields[0].trim_end_matches(':');
-
-            match fields[1].parse() {
-                Ok(value) => (name, Monkey::Number(value)),
-                Err(_) => {
-                    let operator = match fields[2] {
-                        "-" => Operator::Sub,
-                        "+" => Operator::Add,
-                        "/" => Operator::Div,
-                        "*" => Operator::Mul,
-                        _ => unreachable!(),
-                    };
-
-                    (name, Monkey::Operation(fields[1], operator, fields[3]))
-                }
+fn parse_input(input: &str) -> Result<HashMap<String, Monkey>, ParseError> {
+    let monkeys = io::parse_lines(input, |line| {
+        let (name, job) = line
+            .split_once(": ")
+            .ok_or_else(|| ParseError::new("<name>: <job>"))?;
+
+        let monkey = match job.split(' ').collect::<Vec<_>>()[..] {
+            [value] => Monkey::Number(io::parse_token(line, value)?),
+            [lhs, operator, rhs] => {
+                let operator = match operator {
+                    "-" => Operator::Sub,
+                    "+" => Operator::Add,
+                    "/" => Operator::Div,
+                    "*" => Operator::Mul,
+                    _ => {
+                        return Err(
+                            ParseError::new("an operator (+, -, * or /)").at_token(line, operator)
+                        )
+                    }
+                };
+
+                Monkey::Operation(lhs.to_string(), operator, rhs.to_string())
             }
-        })
-        .collect()
+            _ => return Err(ParseError::new("a number or an operation").at_token(line, job)),
+        };
+
+        Ok((name.to_string(), monkey))
+    })?;
+
+    Ok(monkeys.into_iter().collect())
 }
 
-fn yell(monkey: &str, monkeys: &HashMap<&str, Monkey>) -> i64 {
+fn yell(monkey: &str, monkeys: &HashMap<String, Monkey>) -> i64 {
     match &monkeys[monkey] {
         Monkey::Number(value) => *value,
         Monkey::Operation(lhs, operator, rhs) => {
@@ -81,16 +90,13 @@ fn yell(monkey: &str, monkeys: &HashMap<&str, Monkey>) -> i64 {
     }
 }
 
-fn part01(path: &str) -> i64 {
-    let input = std::fs::read_to_string(path).expect("expected file");
-    let monkeys = parse_input(&input);
-
-    yell("root", &monkeys)
+pub fn part01(monkeys: &HashMap<String, Monkey>) -> i64 {
+    yell("root", monkeys)
 }
 
 const HUMAN: &str = "humn";
 
-fn contains_human(name: &str, monkeys: &HashMap<&str, Monkey>) -> bool {
+fn contains_human(name: &str, monkeys: &HashMap<String, Monkey>) -> bool {
     if name == HUMAN {
         return true;
     }
@@ -103,7 +109,7 @@ fn contains_human(name: &str, monkeys: &HashMap<&str, Monkey>) -> bool {
     }
 }
 
-fn yell_human(name: &str, value: i64, monkeys: &HashMap<&str, Monkey>) -> i64 {
+fn yell_human(name: &str, value: i64, monkeys: &HashMap<String, Monkey>) -> i64 {
     if name == HUMAN {
         return value;
     }
@@ -126,49 +132,39 @@ fn yell_human(name: &str, value: i64, monkeys: &HashMap<&str, Monkey>) -> i64 {
     }
 }
 
-fn part02(path: &str) -> i64 {
-    let input = std::fs::read_to_string(path).expect("expected file");
-    let monkeys = parse_input(&input);
-
-    let (lhs, rhs) = match monkeys["root"] {
+pub fn part02(monkeys: &HashMap<String, Monkey>) -> i64 {
+    let (lhs, rhs) = match &monkeys["root"] {
         Monkey::Operation(lhs, _, rhs) => (lhs, rhs),
         _ => panic!(),
     };
 
-    let (name, value) = if contains_human(lhs, &monkeys) {
-        let value = yell(rhs, &monkeys);
+    let (name, value) = if contains_human(lhs, monkeys) {
+        let value = yell(rhs, monkeys);
 
         (lhs, value)
     } else {
-        let value = yell(lhs, &monkeys);
+        let value = yell(lhs, monkeys);
 
         (rhs, value)
     };
 
-    yell_human(name, value, &monkeys)
+    yell_human(name, value, monkeys)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day21;
 
-    #[test]
-    fn part01_example() {
-        assert_eq!(part01("data/y2022/day21-example.txt"), 152);
-    }
+impl Solution for Day21 {
+    type Input = HashMap<String, Monkey>;
 
-    #[test]
-    fn part01_input() {
-        assert_eq!(part01("data/y2022/day21.txt"), 223971851179174);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn part02_example() {
-        assert_eq!(part02("data/y2022/day21-example.txt"), 301);
+    fn part1(input: &Self::Input) -> Answer {
+        part01(input).into()
     }
 
-    #[test]
-    fn part02_input() {
-        assert_eq!(part02("data/y2022/day21.txt"), 3379022190351);
+    fn part2(input: &Self::Input) -> Answer {
+        part02(input).into()
     }
 }
@@ -1,159 +1,135 @@
+use crate::error::ParseError;
+use crate::grid::{Direction, Grid, Position2D};
+use crate::io;
+use crate::solution::{Answer, Solution};
 use itertools::Itertools;
 
-#[derive(Debug, PartialEq, Eq)]
-enum Tile {
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Tile {
     Open,
     Solid,
     None,
 }
 
 #[derive(Debug)]
-enum Turn {
+pub enum Turn {
     Left,
     Right,
 }
 
 #[derive(Debug)]
-enum Instruction {
+pub enum Instruction {
     Rotate(Turn),
     Forward(i32),
 }
 
-#[derive(Debug, Clone, Copy)]
-enum Direction {
-    Left,
-    Right,
-    Up,
-    Down,
-}
-
-impl Direction {
-    fn turn(&self, turn: &Turn) -> Self {
-        match (self, turn) {
-            (Direction::Left, Turn::Left) => Direction::Down,
-            (Direction::Left, Turn::Right) => Direction::Up,
-            (Direction::Right, Turn::Left) => Direction::Up,
-            (Direction::Right, Turn::Right) => Direction::Down,
-            (Direction::Up, Turn::Left) => Direction::Left,
-            (Direction::Up, Turn::Right) => Direction::Right,
-            (Direction::Down, Turn::Left) => Direction::Right,
-            (Direction::Down, Turn::Right) => Direction::Left,
-        }
-    }
-
-    fn step(&self) -> Pos {
+impl Turn {
+    const fn apply(&self, direction: Direction) -> Direction {
         match self {
-            Direction::Left => Pos(-1, 0),
-            Direction::Right => Pos(1, 0),
-            Direction::Up => Pos(0, -1),
-            Direction::Down => Pos(0, 1),
-        }
-    }
-
-    fn score(&self) -> i32 {
-        match self {
-            Direction::Right => 0,
-            Direction::Down => 1,
-            Direction::Left => 2,
-            Direction::Up => 3,
+            Turn::Left => direction.turn_left(),
+            Turn::Right => direction.turn_right(),
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
-struct Pos(i32, i32);
-
-impl std::ops::Add for Pos {
-    type Output = Pos;
-
-    fn add(self, other: Pos) -> Pos {
-        Pos(self.0 + other.0, self.1 + other.1)
+fn facing(direction: Direction) -> i32 {
+    match direction {
+        Direction::Right => 0,
+        Direction::Down => 1,
+        Direction::Left => 2,
+        Direction::Up => 3,
+        _ => unreachable!("the map only has cardinal directions"),
     }
 }
 
-impl std::ops::Sub for Pos {
-    type Output = Pos;
+type Notes = (Grid<Tile>, Vec<Instruction>);
 
-    fn sub(self, other: Pos) -> Pos {
-        Pos(self.0 - other.0, self.1 - other.1)
-    }
-}
+fn parse_input(input: &str) -> Result<Notes, ParseError> {
+    let (grid, path) = input
+        .split_once("\n\n")
+        .ok_or_else(|| ParseError::new("a map and a path"))?;
 
-fn parse_input(path: &str) -> (Vec<Vec<Tile>>, Vec<Instruction>) {
-    let input = std::fs::read_to_string(path).expect("expected file");
+    let path_line = grid.lines().count() + 2;
+    let mut column = 1;
 
-    let (grid, instructions) = input.split_once("\n\n").unwrap();
-
-    let instructions = instructions
+    let instructions = path
+        .trim_end()
         .chars()
         .chunk_by(|c| c.is_ascii_digit())
         .into_iter()
-        .map(|(_, v)| -> Instruction {
+        .map(|(_, v)| {
             let s = v.collect::<String>();
+            let at = column;
+            column += s.chars().count();
 
             match s.as_str() {
-                "L" => Instruction::Rotate(Turn::Left),
-                "R" => Instruction::Rotate(Turn::Right),
-                _ => Instruction::Forward(s.parse().unwrap()),
+                "L" => Ok(Instruction::Rotate(Turn::Left)),
+                "R" => Ok(Instruction::Rotate(Turn::Right)),
+                _ => s
+                    .parse()
+                    .map(Instruction::Forward)
+                    .map_err(|_| ParseError::new("a number, L or R").at(path_line, at)),
             }
         })
-        .collect();
-
-    let grid = grid
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|el| match el {
-                    ' ' => Tile::None,
-                    '.' => Tile::Open,
-                    '#' => Tile::Solid,
-                    _ => unreachable!(),
-                })
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<_>>();
-
-    (grid, instructions)
-}
-
-fn get(grid: &[Vec<Tile>], position: Pos) -> Option<&Tile> {
-    grid.get(position.1 as usize)
-        .and_then(|row| row.get(position.0 as usize))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let rows = io::parse_lines(grid, |line| {
+        line.chars()
+            .enumerate()
+            .map(|(x, el)| match el {
+                ' ' => Ok(Tile::None),
+                '.' => Ok(Tile::Open),
+                '#' => Ok(Tile::Solid),
+                _ => Err(ParseError::new("a tile (space, . or #)").at_column(x + 1)),
+            })
+            .collect()
+    })?;
+
+    Ok((Grid::from_rows_padded(rows, Tile::None), instructions))
 }
 
-fn wrap_around(grid: &[Vec<Tile>], pos: Pos, direction: Direction) -> (Pos, Direction) {
-    let step = direction.step();
+fn wrap_around(
+    grid: &Grid<Tile>,
+    pos: Position2D,
+    direction: Direction,
+) -> (Position2D, Direction) {
+    let step = direction.to_offset();
 
     let mut curr = pos;
 
-    while let Some(tile) = get(grid, curr - step) {
+    while let Some(tile) = grid.get(curr - step) {
         if *tile == Tile::None {
             break;
         }
-        curr = curr - step;
+        curr -= step;
     }
 
     (curr, direction)
 }
 
-type WrapMap = fn(grid: &[Vec<Tile>], pos: Pos, direction: Direction) -> (Pos, Direction);
+type WrapMap =
+    fn(grid: &Grid<Tile>, pos: Position2D, direction: Direction) -> (Position2D, Direction);
 
-fn move_grid(grid: &[Vec<Tile>], instructions: Vec<Instruction>, wrap: WrapMap) -> i32 {
-    let start_x = grid[0].iter().position(|tile| *tile == Tile::Open).unwrap() as i32;
+fn move_grid(grid: &Grid<Tile>, instructions: &[Instruction], wrap: WrapMap) -> i32 {
+    let start_x = grid
+        .row(0)
+        .iter()
+        .position(|tile| *tile == Tile::Open)
+        .unwrap() as i32;
 
-    let mut pos = Pos(start_x, 0);
+    let mut pos = Position2D::new(start_x, 0);
 
     let mut direction = Direction::Right;
 
-    for instruction in &instructions {
+    for instruction in instructions {
         match instruction {
-            Instruction::Rotate(turn) => direction = direction.turn(turn),
+            Instruction::Rotate(turn) => direction = turn.apply(direction),
             Instruction::Forward(steps) => {
                 for _ in 0..*steps {
-                    let step = direction.step();
+                    let step = direction.to_offset();
 
                     let new_pos = pos + step;
-                    let new_tile = get(grid, new_pos).unwrap_or(&Tile::None);
+                    let new_tile = grid.get(new_pos).unwrap_or(&Tile::None);
 
                     match new_tile {
                         Tile::Solid => break,
@@ -161,7 +137,7 @@ fn move_grid(grid: &[Vec<Tile>], instructions: Vec<Instruction>, wrap: WrapMap)
                         Tile::None => {
                             let (new_pos, dir) = wrap(grid, pos, direction);
 
-                            if let Some(Tile::Solid) = get(grid, new_pos) {
+                            if let Some(Tile::Solid) = grid.get(new_pos) {
                                 break;
                             }
 
@@ -174,17 +150,15 @@ fn move_grid(grid: &[Vec<Tile>], instructions: Vec<Instruction>, wrap: WrapMap)
         }
     }
 
-    1000 * (pos.1 + 1) + 4 * (pos.0 + 1) + direction.score()
+    1000 * (pos.y + 1) + 4 * (pos.x + 1) + facing(direction)
 }
 
-fn part01(path: &str) -> i32 {
-    let (grid, instructions) = parse_input(path);
-
-    move_grid(&grid, instructions, wrap_around)
+pub fn part01((grid, instructions): &Notes) -> i32 {
+    move_grid(grid, instructions, wrap_around)
 }
 
-fn wrap_cube(_grid: &[Vec<Tile>], pos: Pos, direction: Direction) -> (Pos, Direction) {
-    let (cube_y, cube_x, new_dir) = match (pos.1 / 50, pos.0 / 50, direction) {
+fn wrap_cube(_grid: &Grid<Tile>, pos: Position2D, direction: Direction) -> (Position2D, Direction) {
+    let (cube_y, cube_x, new_dir) = match (pos.y / 50, pos.x / 50, direction) {
         (0, 1, Direction::Up) => (3, 0, Direction::Right),
         (0, 1, Direction::Left) => (2, 0, Direction::Right),
         (0, 2, Direction::Up) => (3, 0, Direction::Up),
@@ -203,13 +177,14 @@ fn wrap_cube(_grid: &[Vec<Tile>], pos: Pos, direction: Direction) -> (Pos, Direc
     };
 
     // find idxes within the cube
-    let (row_idx, col_idx) = (pos.1 % 50, pos.0 % 50);
+    let (row_idx, col_idx) = (pos.y % 50, pos.x % 50);
 
     let i = match direction {
         Direction::Left => 49 - row_idx,
         Direction::Right => row_idx,
         Direction::Up => col_idx,
         Direction::Down => 49 - col_idx,
+        _ => unreachable!(),
     };
 
     // find new idxes within the cube
@@ -218,41 +193,39 @@ fn wrap_cube(_grid: &[Vec<Tile>], pos: Pos, direction: Direction) -> (Pos, Direc
         Direction::Right => i,
         Direction::Up => 49,
         Direction::Down => 0,
+        _ => unreachable!(),
     };
     let new_col = match new_dir {
         Direction::Left => 49,
         Direction::Right => 0,
         Direction::Up => i,
         Direction::Down => 49 - i,
+        _ => unreachable!(),
     };
 
-    let new_pos = Pos(cube_x * 50 + new_col, cube_y * 50 + new_row);
+    let new_pos = Position2D::new(cube_x * 50 + new_col, cube_y * 50 + new_row);
 
     (new_pos, new_dir)
 }
 
-fn part02(path: &str) -> i32 {
-    let (grid, instructions) = parse_input(path);
-
-    move_grid(&grid, instructions, wrap_cube)
+pub fn part02((grid, instructions): &Notes) -> i32 {
+    move_grid(grid, instructions, wrap_cube)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input = Notes;
 
-    #[test]
-    fn part01_example() {
-        assert_eq!(part01("data/y2022/day22-example.txt"), 6032);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn part01_input() {
-        assert_eq!(part01("data/y2022/day22.txt"), 88226);
+    fn part1(input: &Self::Input) -> Answer {
+        part01(input).into()
     }
 
-    #[test]
-    fn part02_input() {
-        assert_eq!(part02("data/y2022/day22.txt"), 57305);
+    fn part2(input: &Self::Input) -> Answer {
+        part02(input).into()
     }
 }
@@ -1,128 +1,91 @@
-use std::{
-    cmp::{max, min},
-    collections::{HashMap, HashSet},
-    hash::Hash,
-};
-
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
-struct Pos(i32, i32);
-
-impl std::ops::Add for Pos {
-    type Output = Pos;
-
-    fn add(self, other: Pos) -> Pos {
-        Pos(self.0 + other.0, self.1 + other.1)
-    }
-}
-
-impl Pos {
-    pub fn neighbours(&self) -> [Pos; 8] {
-        [
-            Pos(self.0 - 1, self.1 - 1),
-            Pos(self.0, self.1 - 1),
-            Pos(self.0 + 1, self.1 - 1),
-            Pos(self.0 - 1, self.1),
-            Pos(self.0 + 1, self.1),
-            Pos(self.0 - 1, self.1 + 1),
-            Pos(self.0, self.1 + 1),
-            Pos(self.0 + 1, self.1 + 1),
-        ]
-    }
-}
-
-fn parse_input(path: &str) -> HashSet<Pos> {
-    std::fs::read_to_string(path)
-        .expect("expected file")
-        .lines()
+use crate::bitgrid::BitGrid;
+use crate::error::ParseError;
+use crate::grid::{self, Direction, Position2D};
+use crate::io;
+use crate::solution::{Answer, Solution};
+
+type Pos = Position2D;
+
+fn parse_input(input: &str) -> Result<BitGrid, ParseError> {
+    let rows = io::parse_lines(input, |line| {
+        line.chars()
+            .enumerate()
+            .filter_map(|(x, char)| match char {
+                '#' => Some(Ok(x as i32)),
+                '.' => None,
+                _ => Some(Err(
+                    ParseError::new("an elf (#) or ground (.)").at_column(x + 1)
+                )),
+            })
+            .collect::<Result<Vec<_>, _>>()
+    })?;
+
+    Ok(rows
+        .iter()
         .enumerate()
-        .flat_map(|(y, line)| {
-            line.chars()
-                .enumerate()
-                .filter_map(|(x, char)| {
-                    if char == '#' {
-                        Some(Pos(x as i32, y as i32))
-                    } else {
-                        None
-                    }
-                })
-                .collect::<Vec<_>>()
-        })
-        .collect()
+        .flat_map(|(y, row)| row.iter().map(move |&x| Pos::new(x, y as i32)))
+        .collect())
 }
 
-const DIRECTIONS: [[Pos; 3]; 4] = [
-    [Pos(0, -1), Pos(-1, -1), Pos(1, -1)], // N
-    [Pos(0, 1), Pos(-1, 1), Pos(1, 1)],    // S
-    [Pos(-1, 0), Pos(-1, -1), Pos(-1, 1)], // W
-    [Pos(1, 0), Pos(1, -1), Pos(1, 1)],    // E
+const DIRECTIONS: [[Direction; 3]; 4] = [
+    [Direction::Up, Direction::UpLeft, Direction::UpRight],
+    [Direction::Down, Direction::DownLeft, Direction::DownRight],
+    [Direction::Left, Direction::UpLeft, Direction::DownLeft],
+    [Direction::Right, Direction::UpRight, Direction::DownRight],
 ];
 
-fn round(mut elves: HashSet<Pos>, round: usize) -> (HashSet<Pos>, bool) {
-    let movers = elves
-        .iter()
-        .filter(|elf| elf.neighbours().iter().any(|n| elves.contains(n)))
-        .filter_map(|elf| {
-            for dir in round..round + 4 {
-                let dirs = DIRECTIONS[dir % 4];
-
-                if !dirs.iter().any(|d| elves.contains(&(*elf + *d))) {
-                    return Some((*elf, *elf + dirs[0]));
-                }
-            }
-            None
-        })
-        .collect::<HashMap<Pos, Pos>>();
-
-    let safe = movers
-        .iter()
-        .fold(HashMap::new(), |mut acc, (_, v)| {
-            acc.entry(v).and_modify(|count| *count += 1).or_insert(1);
-            acc
-        })
-        .into_iter()
-        .filter_map(|(k, v)| if v == 1 { Some(*k) } else { None })
-        .collect::<HashSet<_>>();
+// every elf at once: the elves with a neighbour propose the first free
+// direction and move unless another elf proposed the same cell, which can
+// only be the elf two cells further in that direction
+fn round(elves: &BitGrid, round: usize) -> (BitGrid, bool) {
+    // the cells whose neighbour in a direction is an elf
+    let near = Direction::ALL.map(|d| elves.shifted(d.turn_around()));
+    let crowded = near.iter().skip(1).fold(near[0].clone(), |acc, n| &acc | n);
+
+    let mut waiting = elves & &crowded;
+    let mut targets = [(); 4].map(|_| BitGrid::new());
+
+    for dir in round..round + 4 {
+        let dirs = DIRECTIONS[dir % 4];
+        let [ahead, left, right] = dirs.map(|d| &near[d as usize]);
+        let blocked = &(ahead | left) | right;
+
+        let movers = &waiting - &blocked;
+        waiting = &waiting - &movers;
+        targets[dir % 4] = movers.shifted(dirs[0]);
+    }
 
+    let mut elves = elves.clone();
     let mut moved = false;
 
-    movers
-        .iter()
-        .filter(|(_, v)| safe.contains(v))
-        .for_each(|(from, to)| {
-            elves.remove(from);
-            elves.insert(*to);
-            moved = true;
-        });
+    for (i, dirs) in DIRECTIONS.iter().enumerate() {
+        // up and down, left and right
+        let safe = &targets[i] - &targets[i ^ 1];
+
+        moved |= !safe.is_empty();
+        elves = &(&elves - &safe.shifted(dirs[0].turn_around())) | &safe;
+    }
 
     (elves, moved)
 }
 
-fn part01(path: &str) -> i32 {
-    let mut elves = parse_input(path);
+pub fn part01(elves: &BitGrid) -> i32 {
+    let mut elves = elves.clone();
 
     for r in 0..10 {
-        (elves, _) = round(elves, r);
+        (elves, _) = round(&elves, r);
     }
 
-    let (min_x, max_x, min_y, max_y) = elves.iter().fold(
-        (i32::MAX, i32::MIN, i32::MAX, i32::MIN),
-        |(min_x, max_x, min_y, max_y), &pos| {
-            (
-                min(min_x, pos.0),
-                max(max_x, pos.0),
-                min(min_y, pos.1),
-                max(max_y, pos.1),
-            )
-        },
-    );
-    (max_x - min_x + 1) * (max_y - min_y + 1) - elves.len() as i32
+    let (min, max) = grid::bounding_box(elves.iter()).unwrap();
+
+    (max.x - min.x + 1) * (max.y - min.y + 1) - elves.len() as i32
 }
 
-fn part02(path: &str) -> i32 {
-    let mut elves = parse_input(path);
+pub fn part02(elves: &BitGrid) -> i32 {
+    let mut elves = elves.clone();
 
     for r in 0.. {
-        let (new_elves, moved) = round(elves, r);
+        let (new_elves, moved) = round(&elves, r);
 
         if !moved {
             return r as i32 + 1;
@@ -134,27 +97,20 @@ fn part02(path: &str) -> i32 {
     unreachable!()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day23;
 
-    #[test]
-    fn part01_example() {
-        assert_eq!(part01("data/y2022/day23-example.txt"), 110);
-    }
+impl Solution for Day23 {
+    type Input = BitGrid;
 
-    #[test]
-    fn part01_input() {
-        assert_eq!(part01("data/y2022/day23.txt"), 3762);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn part02_example() {
-        assert_eq!(part02("data/y2022/day23-example.txt"), 20);
+    fn part1(input: &Self::Input) -> Answer {
+        part01(input).into()
     }
 
-    #[test]
-    fn part02_input() {
-        assert_eq!(part02("data/y2022/day23.txt"), 997);
+    fn part2(input: &Self::Input) -> Answer {
+        part02(input).into()
     }
 }
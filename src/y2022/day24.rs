@@ -1,33 +1,14 @@
+use crate::error::ParseError;
+use crate::grid::{Boundary, Direction, Position2D};
+use crate::io;
+use crate::search;
+use crate::solution::{Answer, Solution};
 use std::{
-    collections::{BinaryHeap, HashMap, HashSet},
+    collections::{HashMap, HashSet},
     iter,
 };
 
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
-struct Pos(i32, i32);
-
-impl std::ops::Add for Pos {
-    type Output = Pos;
-
-    fn add(self, other: Pos) -> Pos {
-        Pos(self.0 + other.0, self.1 + other.1)
-    }
-}
-
-impl Pos {
-    pub fn neighbours(&self) -> [Pos; 4] {
-        [
-            Pos(self.0 - 1, self.1),
-            Pos(self.0 + 1, self.1),
-            Pos(self.0, self.1 - 1),
-            Pos(self.0, self.1 + 1),
-        ]
-    }
-
-    pub fn distance(&self, rhs: &Pos) -> i32 {
-        (self.0 - rhs.0).abs() + (self.0 - rhs.0).abs()
-    }
-}
+type Pos = Position2D;
 
 #[derive(Debug, PartialEq, Eq)]
 enum Tile {
@@ -35,25 +16,6 @@ enum Tile {
     Blizzard(Direction),
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
-enum Direction {
-    Up,
-    Right,
-    Down,
-    Left,
-}
-
-impl Direction {
-    fn step(&self) -> Pos {
-        match self {
-            Self::Left => Pos(-1, 0),
-            Self::Right => Pos(1, 0),
-            Self::Up => Pos(0, -1),
-            Self::Down => Pos(0, 1),
-        }
-    }
-}
-
 #[derive(Debug, PartialEq, Eq)]
 struct Map {
     map: HashMap<Pos, Tile>,
@@ -103,18 +65,18 @@ fn bliz_maps(
     let coords = blizzards.iter().map(|(coord, _)| *coord).collect();
     cache.insert(0, coords);
 
+    // blizzards reaching a wall reappear on the other side of the valley
+    let valley = Boundary::Wrap {
+        min: Pos::new(1, 1),
+        max: Pos::new(cols - 2, rows - 2),
+    };
+
     // precompute every blizzard coord at every time before the coords repeat
     for time in 1..max_time {
         for (pos, dir) in blizzards.iter_mut() {
-            *pos = *pos + dir.step();
-            // if next pos went to an edge, wrap
-            match (dir, pos.0, pos.1) {
-                (Direction::Left, 0, _) => pos.0 = cols - 2,
-                (Direction::Right, x, _) if x == cols - 1 => pos.0 = 1,
-                (Direction::Up, _, 0) => pos.1 = rows - 2,
-                (Direction::Down, _, y) if y == rows - 1 => pos.1 = 1,
-                _ => (),
-            }
+            *pos = valley
+                .resolve(*pos + *dir)
+                .expect("wrapping keeps blizzards in the valley");
         }
         let coords = blizzards.iter().map(|(coord, _)| *coord).collect();
         cache.insert(time, coords);
@@ -123,61 +85,50 @@ fn bliz_maps(
     cache
 }
 
-fn parse_input(path: &str) -> Map {
-    let input = std::fs::read_to_string(path).expect("expected file");
-
+fn parse_input(input: &str) -> Result<Map, ParseError> {
     let max_y = input.lines().count() as i32;
-    let max_x = input.lines().next().unwrap().chars().count() as i32;
-
-    let map = input
+    let max_x = input
         .lines()
+        .next()
+        .ok_or_else(|| ParseError::new("a map"))?
+        .chars()
+        .count() as i32;
+
+    let rows = io::parse_lines(input, |line| {
+        line.chars()
+            .enumerate()
+            .filter_map(|(x, char)| {
+                let tile = match char {
+                    '#' => Tile::Wall,
+                    '.' => return None,
+                    c => match Direction::try_from(c) {
+                        Ok(direction) => Tile::Blizzard(direction),
+                        Err(_) => {
+                            return Some(Err(
+                                ParseError::new("a wall, a blizzard or ground").at_column(x + 1)
+                            ))
+                        }
+                    },
+                };
+
+                Some(Ok((x as i32, tile)))
+            })
+            .collect::<Result<Vec<_>, _>>()
+    })?;
+
+    let map = rows
+        .into_iter()
         .enumerate()
-        .flat_map(|(y, line)| {
-            line.chars()
-                .enumerate()
-                .filter_map(|(x, char)| {
-                    let pos = Pos(x as i32, y as i32);
-
-                    let tile = match char {
-                        '>' => Tile::Blizzard(Direction::Right),
-                        '<' => Tile::Blizzard(Direction::Left),
-                        '^' => Tile::Blizzard(Direction::Up),
-                        'v' => Tile::Blizzard(Direction::Down),
-                        '#' => Tile::Wall,
-                        _ => return None,
-                    };
-
-                    Some((pos, tile))
-                })
-                .collect::<Vec<_>>()
+        .flat_map(|(y, row)| {
+            row.into_iter()
+                .map(move |(x, tile)| (Pos::new(x, y as i32), tile))
         })
         .collect();
 
-    Map { map, max_x, max_y }
+    Ok(Map { map, max_x, max_y })
 }
 
-#[derive(PartialEq, Eq)]
-struct Node {
-    cost: i32,
-    heuristic: i32,
-    pos: Pos,
-}
-
-impl Ord for Node {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        let self_total = self.cost + self.heuristic;
-        let other_total = other.cost + other.heuristic;
-        other_total.cmp(&self_total)
-    }
-}
-
-impl PartialOrd for Node {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-struct MapInfo {
+pub struct MapInfo {
     max_y: i32,
     max_x: i32,
     walls: HashSet<Pos>,
@@ -194,56 +145,43 @@ fn shortest(from: Pos, to: Pos, start_time: i32, map_info: &MapInfo) -> i32 {
         repeats_at,
     } = map_info;
 
-    let mut pq = BinaryHeap::new();
-    // backtracking is allowed, keep track of visited coords at a certain time
-    let mut seen = HashSet::new();
-
-    pq.push(Node {
-        cost: start_time,
-        heuristic: from.distance(&to),
-        pos: from,
-    });
-
-    seen.insert((from, start_time));
-
-    // keep stepping through time until the priority queue is empty
-    while let Some(Node { cost, pos, .. }) = pq.pop() {
-        // did we pop a node that's at the target position? It's guaranteed to be the shortest path
-        if pos == to {
-            return cost;
-        }
+    let bounds = Boundary::Clip {
+        min: Pos::new(0, 0),
+        max: Pos::new(*max_x, *max_y),
+    };
 
-        let new_cost = cost + 1;
-        let blizzards = &blizzard_maps[&(new_cost % repeats_at)];
+    // backtracking is allowed, so a state is a coord at a point in the
+    // cycle of blizzard positions, and every step takes a minute
+    let neighbours = |(pos, time): (Pos, i32)| {
+        let time = (time + 1) % repeats_at;
+        let blizzards = &blizzard_maps[&time];
 
-        let candidates = pos
+        pos
             // moving to a neighbour is an option
-            .neighbours()
+            .neighbours4_within(bounds)
             .into_iter()
             // not moving is an option
             .chain(iter::once(pos))
             // can not share a coordinate with a wall
-            .filter(|pos| pos.0 >= 0 && pos.1 >= 0 && pos.0 <= *max_x && pos.1 <= *max_y)
             .filter(|coord| !walls.contains(coord))
             // can not share a coordinate with a blizzard
-            .filter(|coord| !blizzards.contains(coord));
-
-        for new_pos in candidates {
-            // only push to pq if we didn't already see that coord at the same time
-            if seen.insert((new_pos, new_cost)) {
-                pq.push(Node {
-                    cost: new_cost,
-                    heuristic: new_pos.distance(&to),
-                    pos: new_pos,
-                });
-            }
-        }
-    }
-    i32::MAX
+            .filter(|coord| !blizzards.contains(coord))
+            .map(|coord| ((coord, time), 1))
+            .collect::<Vec<_>>()
+    };
+
+    // every minute gets at most one coord closer
+    let heuristic = |(pos, _): (Pos, i32)| pos.manhattan(&to);
+
+    let start = (from, start_time % repeats_at);
+    let (minutes, _) = search::astar(start, |(pos, _)| pos == to, neighbours, heuristic);
+    let minutes = minutes.expect("the valley should have a way through");
+
+    start_time + minutes
 }
 
-fn part01(path: &str) -> i32 {
-    let map = parse_input(path);
+fn read_input(input: &str) -> Result<MapInfo, ParseError> {
+    let map = parse_input(input)?;
 
     let walls: HashSet<Pos> = map
         .map
@@ -256,72 +194,47 @@ fn part01(path: &str) -> i32 {
     let lcm = lcm(map.max_y - 2, map.max_x - 2);
 
     let blizzard_maps = bliz_maps(&map.map, map.max_y, map.max_x, lcm);
-    let start = Pos(1, 0);
-    let end = Pos(map.max_x - 2, map.max_y - 1);
 
-    let map_info = MapInfo {
+    Ok(MapInfo {
         max_y: map.max_y,
         max_x: map.max_x,
         repeats_at: lcm,
         walls,
         blizzard_maps,
-    };
-
-    shortest(start, end, 0, &map_info)
+    })
 }
 
-fn part02(path: &str) -> i32 {
-    let map = parse_input(path);
+pub fn part01(map_info: &MapInfo) -> i32 {
+    let start = Pos::new(1, 0);
+    let end = Pos::new(map_info.max_x - 2, map_info.max_y - 1);
 
-    let walls: HashSet<Pos> = map
-        .map
-        .iter()
-        .filter(|(_, tile)| **tile == Tile::Wall)
-        .map(|(pos, _)| *pos)
-        .collect();
-
-    // lcm of inner area without the walls. patterns repeat every lcm steps
-    let lcm = lcm(map.max_y - 2, map.max_x - 2);
-
-    let blizzard_maps = bliz_maps(&map.map, map.max_y, map.max_x, lcm);
-    let start = Pos(1, 0);
-    let end = Pos(map.max_x - 2, map.max_y - 1);
+    shortest(start, end, 0, map_info)
+}
 
-    let map_info = MapInfo {
-        max_y: map.max_y,
-        max_x: map.max_x,
-        repeats_at: lcm,
-        walls,
-        blizzard_maps,
-    };
+pub fn part02(map_info: &MapInfo) -> i32 {
+    let start = Pos::new(1, 0);
+    let end = Pos::new(map_info.max_x - 2, map_info.max_y - 1);
 
-    let there = shortest(start, end, 0, &map_info);
-    let back = shortest(end, start, there, &map_info);
+    let there = shortest(start, end, 0, map_info);
+    let back = shortest(end, start, there, map_info);
 
-    shortest(start, end, back, &map_info)
+    shortest(start, end, back, map_info)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day24;
 
-    #[test]
-    fn part01_example() {
-        assert_eq!(part01("data/y2022/day24-example.txt"), 18);
-    }
+impl Solution for Day24 {
+    type Input = MapInfo;
 
-    #[test]
-    fn part01_input() {
-        assert_eq!(part01("data/y2022/day24.txt"), 322);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_input(input)
     }
 
-    #[test]
-    fn part02_example() {
-        assert_eq!(part02("data/y2022/day24-example.txt"), 54);
+    fn part1(input: &Self::Input) -> Answer {
+        part01(input).into()
     }
 
-    #[test]
-    fn part02_input() {
-        assert_eq!(part02("data/y2022/day24.txt"), 974);
+    fn part2(input: &Self::Input) -> Answer {
+        part02(input).into()
     }
 }
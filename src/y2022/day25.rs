@@ -1,11 +1,19 @@
-fn from_snafu(s: &str) -> i64 {
-    s.chars().fold(0, |carry, char| {
-        (carry * 5)
-            + match char {
-                '-' => -1,
-                '=' => -2,
-                _ => char.to_digit(10).unwrap() as i64,
-            }
+use crate::error::ParseError;
+use crate::io;
+use crate::solution::{Answer, Solution};
+
+fn from_snafu(s: &str) -> Result<i64, ParseError> {
+    s.chars().enumerate().try_fold(0, |carry, (x, char)| {
+        let digit = match char {
+            '=' => -2,
+            '-' => -1,
+            '0' => 0,
+            '1' => 1,
+            '2' => 2,
+            _ => return Err(ParseError::new("a snafu digit (=, -, 0, 1 or 2)").at_column(x + 1)),
+        };
+
+        Ok(carry * 5 + digit)
     })
 }
 
@@ -45,30 +53,26 @@ impl Iterator for Snafu {
     }
 }
 
-fn part01(path: &str) -> String {
-    let sum = std::fs::read_to_string(path)
-        .expect("should be able to read file")
-        .lines()
-        .map(from_snafu)
-        .sum();
+fn parse_input(input: &str) -> Result<Vec<i64>, ParseError> {
+    io::parse_lines(input, from_snafu)
+}
+
+pub fn part01(numbers: &[i64]) -> String {
+    let sum = numbers.iter().sum();
 
     Snafu::new(sum).collect::<Vec<_>>().iter().rev().collect()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Input = Vec<i64>;
 
-    #[test]
-    fn part01_example() {
-        assert_eq!(part01("data/y2022/day25-example.txt"), "2=-1=0".to_string())
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn part01_input() {
-        assert_eq!(
-            part01("data/y2022/day25.txt"),
-            "2011-=2=-1020-1===-1".to_string()
-        )
+    fn part1(input: &Self::Input) -> Answer {
+        part01(input).into()
     }
 }
@@ -1,3 +1,5 @@
+use crate::registry::Puzzle;
+
 mod day01;
 mod day02;
 mod day03;
@@ -23,3 +25,33 @@ mod day22;
 mod day23;
 mod day24;
 mod day25;
+
+pub fn puzzles() -> Vec<Puzzle> {
+    vec![
+        Puzzle::new::<day01::Day01>(2022, 1),
+        Puzzle::new::<day02::Day02>(2022, 2),
+        Puzzle::new::<day03::Day03>(2022, 3),
+        Puzzle::new::<day04::Day04>(2022, 4),
+        Puzzle::new::<day05::Day05>(2022, 5),
+        Puzzle::new::<day06::Day06>(2022, 6),
+        Puzzle::new::<day07::Day07>(2022, 7),
+        Puzzle::new::<day08::Day08>(2022, 8),
+        Puzzle::new::<day09::Day09>(2022, 9),
+        Puzzle::new::<day10::Day10>(2022, 10),
+        Puzzle::new::<day11::Day11>(2022, 11),
+        Puzzle::new::<day12::Day12>(2022, 12),
+        Puzzle::new::<day13::Day13>(2022, 13),
+        Puzzle::new::<day14::Day14>(2022, 14),
+        Puzzle::new::<day15::Day15>(2022, 15),
+        Puzzle::new::<day16::Day16>(2022, 16),
+        Puzzle::new::<day17::Day17>(2022, 17),
+        Puzzle::new::<day18::Day18>(2022, 18),
+        Puzzle::new::<day19::Day19>(2022, 19),
+        Puzzle::new::<day20::Day20>(2022, 20),
+        Puzzle::new::<day21::Day21>(2022, 21),
+        Puzzle::new::<day22::Day22>(2022, 22),
+        Puzzle::new::<day23::Day23>(2022, 23),
+        Puzzle::new::<day24::Day24>(2022, 24),
+        Puzzle::new::<day25::Day25>(2022, 25),
+    ]
+}
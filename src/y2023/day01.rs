@@ -1,3 +1,6 @@
+use crate::error::ParseError;
+use crate::solution::{Answer, Solution};
+
 struct Windows<'a> {
     curr: usize,
     data: &'a str,
@@ -30,10 +33,9 @@ fn calibration_value(calibration: &[Calibration], v: &str) -> Option<i32> {
     calibration.iter().find(|Calibration(s, _)| v.starts_with(s)).map(|c| c.1)
 }
 
-fn calibrate(calibration: &[Calibration], path: &str) -> i32 {
-    std::fs::read_to_string(path)
-        .expect("expected file")
-        .lines()
+fn calibrate(calibration: &[Calibration], document: &[String]) -> i32 {
+    document
+        .iter()
         .map(|line| {
             Windows::new(line)
                 .filter_map(|line| calibration_value(calibration, line))
@@ -44,7 +46,11 @@ fn calibrate(calibration: &[Calibration], path: &str) -> i32 {
         })
 }
 
-pub fn part01(path: &str) -> i32 {
+fn parse_input(input: &str) -> Result<Vec<String>, ParseError> {
+    Ok(input.lines().map(str::to_string).collect())
+}
+
+pub fn part01(document: &[String]) -> i32 {
     let calibration = vec![
         Calibration("1", 1),
         Calibration("2", 2),
@@ -57,10 +63,10 @@ pub fn part01(path: &str) -> i32 {
         Calibration("9", 9),
     ];
 
-    calibrate(&calibration, path)
+    calibrate(&calibration, document)
 }
 
-pub fn part02(path: &str) -> i32 {
+pub fn part02(document: &[String]) -> i32 {
     let calibration = vec![
         Calibration("one", 1),
         Calibration("1", 1),
@@ -82,30 +88,23 @@ pub fn part02(path: &str) -> i32 {
         Calibration("9", 9),
     ];
 
-    calibrate(&calibration, path)
+    calibrate(&calibration, document)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day01;
 
-    #[test]
-    fn part01_example() {
-        assert_eq!(part01("data/y2023/day01-example1.txt"), 142);
-    }
+impl Solution for Day01 {
+    type Input = Vec<String>;
 
-    #[test]
-    fn part01_input() {
-        assert_eq!(part01("data/y2023/day01.txt"), 54708);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn part02_example() {
-        assert_eq!(part02("data/y2023/day01-example2.txt"), 281);
+    fn part1(input: &Self::Input) -> Answer {
+        part01(input).into()
     }
 
-    #[test]
-    fn part02_input() {
-        assert_eq!(part02("data/y2023/day01.txt"), 54087);
+    fn part2(input: &Self::Input) -> Answer {
+        part02(input).into()
     }
 }
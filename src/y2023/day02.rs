@@ -1,4 +1,6 @@
+use crate::error::ParseError;
 use crate::io;
+use crate::solution::{Answer, Solution};
 use std::cmp;
 use std::collections::HashMap;
 use std::str::FromStr;
@@ -11,21 +13,26 @@ enum Cube {
 }
 
 impl FromStr for Cube {
-    type Err = &'static str;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        s.split_once(' ').ok_or("invalid input").and_then(|(count, colour)| {
-            let count = count.parse().map_err(|_| "invalid count")?;
-
-            let cube = match colour {
-                "red" => Self::Red(count),
-                "green" => Self::Green(count),
-                "blue" => Self::Blue(count),
-                _ => return Err("invalid colour"),
-            };
-
-            Ok(cube)
-        })
+        let (count, colour) = s
+            .split_once(' ')
+            .ok_or_else(|| ParseError::new("<count> <colour>"))?;
+        let count = io::parse_token(s, count)?;
+
+        let cube = match colour {
+            "red" => Self::Red(count),
+            "green" => Self::Green(count),
+            "blue" => Self::Blue(count),
+            _ => {
+                return Err(
+                    ParseError::new("red, green or blue").at_token(s, colour)
+                )
+            }
+        };
+
+        Ok(cube)
     }
 }
 
@@ -45,33 +52,45 @@ impl Cube {
     }
 }
 
-fn parse_set(s: &str) -> Vec<Cube> {
+fn parse_set(s: &str) -> Result<Vec<Cube>, ParseError> {
     s.split(',')
-        .filter_map(|v| v.trim_start_matches(' ').parse::<Cube>().ok())
+        .map(|v| {
+            let v = v.trim_start_matches(' ');
+
+            v.parse::<Cube>().map_err(|err| err.at_token(s, v))
+        })
         .collect()
 }
 
 #[derive(Debug, PartialEq)]
-struct Game(u32, Vec<Vec<Cube>>);
+pub struct Game(u32, Vec<Vec<Cube>>);
 
 impl FromStr for Game {
-    type Err = &'static str;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        s.split_once(": ").ok_or("invalid input").and_then(|(game, sets)| {
-            let game = game.split_once(' ').ok_or("invalid game").and_then(
-                |(_, game)| game.parse().map_err(|_| "invalid game"),
-            )?;
-
-            let sets = sets.split(';').map(parse_set).collect();
-
-            Ok(Self(game, sets))
-        })
+        let (game, sets) = s
+            .split_once(": ")
+            .ok_or_else(|| ParseError::new("Game <id>: <sets>"))?;
+        let (_, id) = game
+            .split_once(' ')
+            .ok_or_else(|| ParseError::new("Game <id>"))?;
+
+        let sets = sets
+            .split(';')
+            .map(|set| parse_set(set).map_err(|err| err.at_token(s, set)))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self(io::parse_token(s, id)?, sets))
     }
 }
 
-fn part01(path: &str) -> u32 {
-    io::read_value_per_line::<Game>(path)
+fn parse_input(input: &str) -> Result<Vec<Game>, ParseError> {
+    io::parse_value_per_line(input)
+}
+
+pub fn part01(games: &[Game]) -> u32 {
+    games
         .iter()
         .filter(|game| {
             game.1.iter().all(|set| {
@@ -86,8 +105,8 @@ fn part01(path: &str) -> u32 {
         .sum()
 }
 
-fn part02(path: &str) -> u32 {
-    io::read_value_per_line::<Game>(path)
+pub fn part02(games: &[Game]) -> u32 {
+    games
         .iter()
         .map(|game| {
             game.1.iter().flatten().fold(HashMap::new(), |mut map, cube| {
@@ -102,6 +121,24 @@ fn part02(path: &str) -> u32 {
         .sum()
 }
 
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<Game>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        part01(input).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        part02(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -116,24 +153,4 @@ mod tests {
                 vec![Cube::Green(3), Cube::Blue(15), Cube::Red(14)]])
         ));
     }
-
-    #[test]
-    fn part01_example() {
-        assert_eq!(part01("data/y2023/day02-example1.txt"), 8);
-    }
-
-    #[test]
-    fn part01_input() {
-        assert_eq!(part01("data/y2023/day02.txt"), 2476);
-    }
-
-    #[test]
-    fn part02_example() {
-        assert_eq!(part02("data/y2023/day02-example1.txt"), 2286);
-    }
-
-    #[test]
-    fn part02_input() {
-        assert_eq!(part02("data/y2023/day02.txt"), 54911);
-    }
 }
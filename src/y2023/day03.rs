@@ -1,24 +1,25 @@
+use crate::error::ParseError;
 use crate::grid::Position2D;
+use crate::solution::{Answer, Solution};
 use std::collections::HashSet;
 
 #[derive(PartialEq, Debug)]
-enum Schematic {
+pub enum Schematic {
     Symbol(char),
     Gear,
 }
 
-fn parse_input(
-    path: &str,
-) -> (Vec<(u32, Vec<Position2D>)>, Vec<(Schematic, Position2D)>) {
-    let content = std::fs::read_to_string(path).expect("expected file");
+type Part = (u32, Vec<Position2D>);
+type Engine = (Vec<Part>, Vec<(Schematic, Position2D)>);
 
+fn parse_input(input: &str) -> Result<Engine, ParseError> {
     let mut parts = Vec::new();
     let mut symbols = Vec::new();
 
     let mut number = 0;
     let mut positions = Vec::new();
 
-    for (y, line) in content.lines().enumerate() {
+    for (y, line) in input.lines().enumerate() {
         for (x, ch) in line.chars().enumerate() {
             let pos = Position2D {
                 x: x.try_into().expect("expect try_into to work"),
@@ -51,12 +52,10 @@ fn parse_input(
         }
     }
 
-    (parts, symbols)
+    Ok((parts, symbols))
 }
 
-fn part01(path: &str) -> u32 {
-    let (parts, symbols) = parse_input(path);
-
+pub fn part01((parts, symbols): &Engine) -> u32 {
     let symbols =
         symbols.iter().map(|&(_, pos)| pos).collect::<HashSet<Position2D>>();
 
@@ -77,9 +76,7 @@ fn part01(path: &str) -> u32 {
         .sum()
 }
 
-fn part02(path: &str) -> u32 {
-    let (parts, symbols) = parse_input(path);
-
+pub fn part02((parts, symbols): &Engine) -> u32 {
     let symbols = symbols
         .iter()
         .filter_map(|(schematic, pos)| {
@@ -124,27 +121,20 @@ fn part02(path: &str) -> u32 {
     })
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day03;
 
-    #[test]
-    fn part01_example() {
-        assert_eq!(part01("data/y2023/day03-example1.txt"), 4361);
-    }
+impl Solution for Day03 {
+    type Input = Engine;
 
-    #[test]
-    fn part01_input() {
-        assert_eq!(part01("data/y2023/day03.txt"), 512_794);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn part02_example() {
-        assert_eq!(part02("data/y2023/day03-example1.txt"), 467_835);
+    fn part1(input: &Self::Input) -> Answer {
+        part01(input).into()
     }
 
-    #[test]
-    fn part02_input() {
-        assert_eq!(part02("data/y2023/day03.txt"), 67_779_080);
+    fn part2(input: &Self::Input) -> Answer {
+        part02(input).into()
     }
 }
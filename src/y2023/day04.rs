@@ -1,45 +1,51 @@
+use crate::error::ParseError;
 use crate::io;
+use crate::solution::{Answer, Solution};
 use std::collections::HashSet;
 use std::str::FromStr;
 
 #[derive(Debug, PartialEq)]
-struct ScratchCard {
+pub struct ScratchCard {
     id: u32,
     matched_count: usize,
 }
 
 impl FromStr for ScratchCard {
-    type Err = &'static str;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        s.split_once(": ").ok_or("invalid input").and_then(|(id, numbers)| {
-            let id =
-                id.split_once(' ').ok_or("invalid input").map(|(_, id)| {
-                    id.trim().parse::<u32>().expect("valid number")
-                })?;
+        let (id, numbers) = s
+            .split_once(": ")
+            .ok_or_else(|| ParseError::new("Card <id>: <numbers>"))?;
+        let (_, id) =
+            id.split_once(' ').ok_or_else(|| ParseError::new("Card <id>"))?;
+        let (left, right) = numbers.split_once(" | ").ok_or_else(|| {
+            ParseError::new("<winning> | <numbers>").at_token(s, numbers)
+        })?;
 
-            let matched_count = numbers
-                .split_once(" | ")
-                .ok_or("invalid input")
-                .map(|(left, right)| {
-                    let winning: HashSet<_> = left
-                        .split_whitespace()
-                        .filter_map(|s| s.parse::<u32>().ok())
-                        .collect();
-                    let numbers: HashSet<_> = right
-                        .split_whitespace()
-                        .filter_map(|s| s.parse::<u32>().ok())
-                        .collect();
-                    winning.intersection(&numbers).count()
-                })?;
+        let parse_numbers = |numbers: &str| {
+            numbers
+                .split_whitespace()
+                .map(|n| io::parse_token::<u32>(s, n))
+                .collect::<Result<HashSet<_>, _>>()
+        };
 
-            Ok(Self { id, matched_count })
+        let winning = parse_numbers(left)?;
+        let numbers = parse_numbers(right)?;
+
+        Ok(Self {
+            id: io::parse_token(s, id.trim())?,
+            matched_count: winning.intersection(&numbers).count(),
         })
     }
 }
 
-fn part01(path: &str) -> i32 {
-    io::read_value_per_line::<ScratchCard>(path)
+fn parse_input(input: &str) -> Result<Vec<ScratchCard>, ParseError> {
+    io::parse_value_per_line(input)
+}
+
+pub fn part01(scratch_cards: &[ScratchCard]) -> i32 {
+    scratch_cards
         .iter()
         .map(|card| {
             if card.matched_count > 0 {
@@ -51,9 +57,7 @@ fn part01(path: &str) -> i32 {
         .sum()
 }
 
-fn part02(path: &str) -> i32 {
-    let scratch_cards = io::read_value_per_line::<ScratchCard>(path);
-
+pub fn part02(scratch_cards: &[ScratchCard]) -> i32 {
     scratch_cards
         .iter()
         .enumerate()
@@ -68,27 +72,20 @@ fn part02(path: &str) -> i32 {
         .sum()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day04;
 
-    #[test]
-    fn part01_example() {
-        assert_eq!(part01("data/y2023/day04-example1.txt"), 13);
-    }
+impl Solution for Day04 {
+    type Input = Vec<ScratchCard>;
 
-    #[test]
-    fn part01_input() {
-        assert_eq!(part01("data/y2023/day04.txt"), 21088);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn part02_example() {
-        assert_eq!(part02("data/y2023/day04-example1.txt"), 30);
+    fn part1(input: &Self::Input) -> Answer {
+        part01(input).into()
     }
 
-    #[test]
-    fn part02_input() {
-        assert_eq!(part02("data/y2023/day04.txt"), 6_874_754);
+    fn part2(input: &Self::Input) -> Answer {
+        part02(input).into()
     }
 }
@@ -1,3 +1,6 @@
+use crate::error::ParseError;
+use crate::io;
+use crate::solution::{Answer, Solution};
 use std::collections::HashSet;
 use std::str::FromStr;
 
@@ -9,19 +12,19 @@ struct GardenRange {
 }
 
 impl FromStr for GardenRange {
-    type Err = &'static str;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let values = s
-            .split_whitespace()
-            .filter_map(|v| v.parse::<u64>().ok())
-            .collect::<Vec<_>>();
-
-        if values.len() != 3 {
-            return Err("invalid range");
-        }
-
-        Ok(GardenRange { to: values[0], from: values[1], length: values[2] })
+        let [to, from, length] = s.split_whitespace().collect::<Vec<_>>()[..]
+        else {
+            return Err(ParseError::new("a range with three numbers"));
+        };
+
+        Ok(GardenRange {
+            to: io::parse_token(s, to)?,
+            from: io::parse_token(s, from)?,
+            length: io::parse_token(s, length)?,
+        })
     }
 }
 
@@ -40,25 +43,20 @@ impl GardenRange {
 }
 
 #[derive(Debug, PartialEq)]
-struct Almanac(Vec<Vec<GardenRange>>);
+pub struct Almanac(Vec<Vec<GardenRange>>);
 
 impl FromStr for Almanac {
-    type Err = &'static str;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let sections = s
-            .split("\n\n")
-            .map(|section| {
-                section
-                    .split('\n')
-                    .skip(1)
-                    .filter_map(|s| s.parse::<GardenRange>().ok())
-                    .collect::<Vec<_>>()
-            })
-            .collect::<Vec<_>>();
+        let sections = io::parse_chunks(s, |section| {
+            let (_, ranges) = section.split_once('\n').unwrap_or_default();
+
+            io::parse_value_per_line(ranges).map_err(|err| err.below(1))
+        })?;
 
         if sections.len() != 7 {
-            return Err("invalid almanac");
+            return Err(ParseError::new("seven maps"));
         }
 
         Ok(Almanac(sections))
@@ -73,39 +71,33 @@ impl Almanac {
     }
 }
 
-fn parse_input(path: &str) -> (Vec<u64>, Almanac) {
-    let contents =
-        std::fs::read_to_string(path).expect("should be able to read file");
+type Seeds = (Vec<u64>, Almanac);
 
-    contents
+fn parse_input(input: &str) -> Result<Seeds, ParseError> {
+    let (line, almanac) = input
         .split_once("\n\n")
-        .map(|(seeds, almanac)| {
-            let seeds = seeds
-                .split_once(": ")
-                .map(|(_, seeds)| {
-                    seeds
-                        .split_whitespace()
-                        .filter_map(|s| s.parse::<u64>().ok())
-                        .collect::<Vec<_>>()
-                })
-                .expect("parsing seeds to work");
-
-            let almanac = almanac.parse::<Almanac>().expect("almanac");
-
-            (seeds, almanac)
-        })
-        .expect("to parse")
-}
+        .ok_or_else(|| ParseError::new("seeds and an almanac"))?;
 
-fn part01(path: &str) -> u64 {
-    let (seeds, almanac) = parse_input(path);
+    let seeds = line
+        .split_once(": ")
+        .ok_or_else(|| ParseError::new("seeds: <numbers>"))?
+        .1
+        .split_whitespace()
+        .map(|seed| io::parse_token(line, seed))
+        .collect::<Result<Vec<_>, _>>()?;
 
-    seeds.iter().map(|seed| almanac.find(*seed)).min().expect("expected a min")
+    let almanac = almanac
+        .parse::<Almanac>()
+        .map_err(|err| err.below(line.lines().count() + 1))?;
+
+    Ok((seeds, almanac))
 }
 
-fn part02(path: &str) -> u64 {
-    let (seeds, almanac) = parse_input(path);
+pub fn part01((seeds, almanac): &Seeds) -> u64 {
+    seeds.iter().map(|seed| almanac.find(*seed)).min().expect("expected a min")
+}
 
+pub fn part02((seeds, almanac): &Seeds) -> u64 {
     let seeds = seeds
         .chunks(2)
         .flat_map(|chunk| (chunk[0]..chunk[0] + chunk[1]).collect::<Vec<_>>())
@@ -127,28 +119,20 @@ fn part02(path: &str) -> u64 {
         .expect("expected a location")
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day05;
 
-    #[test]
-    fn part01_example() {
-        assert_eq!(part01("data/y2023/day05-example1.txt"), 35);
-    }
+impl Solution for Day05 {
+    type Input = Seeds;
 
-    #[test]
-    fn part01_input() {
-        assert_eq!(part01("data/y2023/day05.txt"), 551_761_867);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn part02_example() {
-        assert_eq!(part02("data/y2023/day05-example1.txt"), 46);
+    fn part1(input: &Self::Input) -> Answer {
+        part01(input).into()
     }
 
-    #[ignore]
-    #[test]
-    fn part02_input() {
-        assert_eq!(part02("data/y2023/day05.txt"), 57_451_709);
+    fn part2(input: &Self::Input) -> Answer {
+        part02(input).into()
     }
 }
@@ -1,3 +1,7 @@
+use crate::error::ParseError;
+use crate::io;
+use crate::solution::{Answer, Solution};
+
 const fn calculate(length: u64, time: u64) -> u64 {
     time * (length - time)
 }
@@ -6,81 +10,75 @@ fn different_ways(length: u64, record: u64) -> usize {
     (1..length).filter(|&time| calculate(length, time) > record).count()
 }
 
-fn parse_input(path: &str) -> Vec<(u64, u64)> {
-    let values = std::fs::read_to_string(path)
-        .expect("should be able to read file")
-        .lines()
-        .map(|line| {
-            line.split_once(':')
-                .map(|(_, values)| {
-                    values
-                        .split_whitespace()
-                        .filter_map(|v| v.parse().ok())
-                        .collect::<Vec<_>>()
-                })
-                .expect("expected values")
-        })
-        .collect::<Vec<_>>();
-
-    values
-        .get(0)
-        .cloned()
-        .unwrap_or_default()
-        .into_iter()
-        .zip(values.get(1).cloned().unwrap_or_default())
-        .collect::<Vec<_>>()
+fn parse_input(input: &str) -> Result<Vec<(u64, u64)>, ParseError> {
+    let values = io::parse_lines(input, |line| {
+        line.split_once(':')
+            .ok_or_else(|| ParseError::new("<name>: <values>"))?
+            .1
+            .split_whitespace()
+            .map(|v| io::parse_token::<u64>(line, v))
+            .collect::<Result<Vec<_>, _>>()
+    })?;
+
+    let [times, distances] = &values[..] else {
+        return Err(ParseError::new(
+            "a line of times and a line of distances",
+        ));
+    };
+
+    Ok(times.iter().copied().zip(distances.iter().copied()).collect())
+}
+
+fn parse_input_part2(input: &str) -> Result<(u64, u64), ParseError> {
+    let values = io::parse_lines(input, |line| {
+        let (_, value) = line
+            .split_once(':')
+            .ok_or_else(|| ParseError::new("<name>: <value>"))?;
+
+        value
+            .replace(' ', "")
+            .parse::<u64>()
+            .map_err(|_| ParseError::new("a number").at_token(line, value))
+    })?;
+
+    let [time, distance] = values[..] else {
+        return Err(ParseError::new("a time and a distance"));
+    };
+
+    Ok((time, distance))
 }
 
-fn parse_input_part2(path: &str) -> (u64, u64) {
-    let values = std::fs::read_to_string(path)
-        .expect("should be able to read file")
-        .lines()
-        .map(|line| {
-            line.split_once(':')
-                .map(|(_, value)| {
-                    value.replace(' ', "").parse::<u64>().expect("value")
-                })
-                .expect("expected values")
-        })
-        .collect::<Vec<_>>();
-
-    (values[0], values[1])
+pub struct Races {
+    races: Vec<(u64, u64)>,
+    race: (u64, u64),
 }
 
-fn part01(path: &str) -> usize {
-    parse_input(path)
-        .iter()
-        .map(|&(time, record)| different_ways(time, record))
-        .product()
+fn read_input(input: &str) -> Result<Races, ParseError> {
+    Ok(Races { races: parse_input(input)?, race: parse_input_part2(input)? })
 }
 
-fn part02(path: &str) -> usize {
-    let (time, record) = parse_input_part2(path);
+pub fn part01(races: &[(u64, u64)]) -> usize {
+    races.iter().map(|&(time, record)| different_ways(time, record)).product()
+}
 
+pub fn part02(&(time, record): &(u64, u64)) -> usize {
     different_ways(time, record)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day06;
 
-    #[test]
-    fn part01_example() {
-        assert_eq!(part01("data/y2023/day06-example1.txt"), 288);
-    }
+impl Solution for Day06 {
+    type Input = Races;
 
-    #[test]
-    fn part01_input() {
-        assert_eq!(part01("data/y2023/day06.txt"), 1_195_150);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_input(input)
     }
 
-    #[test]
-    fn part02_example() {
-        assert_eq!(part02("data/y2023/day06-example1.txt"), 71503);
+    fn part1(input: &Self::Input) -> Answer {
+        part01(&input.races).into()
     }
 
-    #[test]
-    fn part02_input() {
-        assert_eq!(part02("data/y2023/day06.txt"), 42_550_411);
+    fn part2(input: &Self::Input) -> Answer {
+        part02(&input.race).into()
     }
 }
@@ -1,3 +1,6 @@
+use crate::error::ParseError;
+use crate::io;
+use crate::solution::{Answer, Solution};
 use itertools::Itertools;
 
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
@@ -19,7 +22,7 @@ enum Card {
 }
 
 impl TryFrom<char> for Card {
-    type Error = &'static str;
+    type Error = ParseError;
 
     fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
@@ -36,7 +39,7 @@ impl TryFrom<char> for Card {
             '4' => Ok(Self::Four),
             '3' => Ok(Self::Three),
             '2' => Ok(Self::Two),
-            _ => Err("unexpected card"),
+            _ => Err(ParseError::new("a card (A, K, Q, J, T or 2-9)")),
         }
     }
 }
@@ -53,7 +56,7 @@ enum Value {
 }
 
 #[derive(Debug, Clone)]
-struct Hand(Vec<Card>);
+pub struct Hand(Vec<Card>);
 
 impl Hand {
     fn value(&self) -> Value {
@@ -160,29 +163,18 @@ impl PartialOrd for Hand {
     }
 }
 
-fn parse_input(path: &str) -> Vec<(Hand, u32)> {
-    std::fs::read_to_string(path)
-        .expect("should be able to read file")
-        .lines()
-        .map(|line| {
-            line.split_once(' ')
-                .map(|(hand, bid)| {
-                    (
-                        Hand(
-                            hand.chars()
-                                .filter_map(|ch| Card::try_from(ch).ok())
-                                .collect::<Vec<_>>(),
-                        ),
-                        bid.trim().parse().unwrap(),
-                    )
-                })
-                .expect("expected values")
-        })
-        .collect::<Vec<_>>()
+fn parse_input(input: &str) -> Result<Vec<(Hand, u32)>, ParseError> {
+    io::parse_lines(input, |line| {
+        let (hand, bid) = line
+            .split_once(' ')
+            .ok_or_else(|| ParseError::new("a hand and a bid"))?;
+
+        Ok((Hand(io::parse_row(hand)?), io::parse_token(line, bid.trim())?))
+    })
 }
 
-fn part01(path: &str) -> u32 {
-    let mut hands = parse_input(path);
+pub fn part01(hands: &[(Hand, u32)]) -> u32 {
+    let mut hands = hands.to_vec();
 
     hands.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
 
@@ -193,8 +185,8 @@ fn part01(path: &str) -> u32 {
         .sum()
 }
 
-fn part02(path: &str) -> u32 {
-    let mut hands = parse_input(path)
+pub fn part02(hands: &[(Hand, u32)]) -> u32 {
+    let mut hands = hands
         .iter()
         .map(|(card, bid)| {
             (
@@ -221,27 +213,20 @@ fn part02(path: &str) -> u32 {
         .sum()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day07;
 
-    #[test]
-    fn part01_example() {
-        assert_eq!(part01("data/y2023/day07-example1.txt"), 6440);
-    }
+impl Solution for Day07 {
+    type Input = Vec<(Hand, u32)>;
 
-    #[test]
-    fn part01_input() {
-        assert_eq!(part01("data/y2023/day07.txt"), 253_954_294);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn part02_example() {
-        assert_eq!(part02("data/y2023/day07-example1.txt"), 5905);
+    fn part1(input: &Self::Input) -> Answer {
+        part01(input).into()
     }
 
-    #[test]
-    fn part02_input() {
-        assert_eq!(part02("data/y2023/day07.txt"), 254_837_398);
+    fn part2(input: &Self::Input) -> Answer {
+        part02(input).into()
     }
 }
@@ -1,3 +1,6 @@
+use crate::error::ParseError;
+use crate::io;
+use crate::solution::{Answer, Solution};
 use std::collections::HashMap;
 use std::str::FromStr;
 
@@ -8,57 +11,50 @@ enum Instruction {
 }
 
 impl TryFrom<char> for Instruction {
-    type Error = &'static str;
+    type Error = ParseError;
 
     fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
             'L' => Ok(Self::Left),
             'R' => Ok(Self::Right),
-            _ => Err("unexpected instruction"),
+            _ => Err(ParseError::new("an instruction (L or R)")),
         }
     }
 }
 
 #[derive(Debug)]
-struct Network {
+pub struct Network {
     instructions: Vec<Instruction>,
     nodes: HashMap<String, Vec<String>>,
 }
 
 impl FromStr for Network {
-    type Err = &'static str;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        s.split_once("\n\n")
-            .map(|(instructions, nodes)| {
-                let instructions = instructions
-                    .chars()
-                    .map(|ch| {
-                        Instruction::try_from(ch)
-                            .expect("expected instruction")
-                    })
-                    .collect::<Vec<_>>();
-
-                let nodes = nodes
-                    .lines()
-                    .map(|line| {
-                        line.split(['=', ' ', '(', ',', ')'])
-                            .filter(|s| !s.is_empty())
-                            .collect::<Vec<_>>()
-                    })
-                    .fold(HashMap::new(), |mut acc, line| {
-                        acc.entry(line[0].to_string()).or_insert_with(|| {
-                            line[1..]
-                                .iter()
-                                .map(std::string::ToString::to_string)
-                                .collect::<Vec<_>>()
-                        });
-                        acc
-                    });
-
-                Ok(Self { instructions, nodes })
-            })
-            .expect("network")
+        let (instructions, nodes) = s
+            .split_once("\n\n")
+            .ok_or_else(|| ParseError::new("instructions and nodes"))?;
+
+        let instructions = io::parse_row(instructions)?;
+
+        let nodes = io::parse_lines(nodes, |line| {
+            let (node, next) = line.split_once(" = ").ok_or_else(|| {
+                ParseError::new("<node> = (<left>, <right>)")
+            })?;
+            let (left, right) = next
+                .strip_prefix('(')
+                .and_then(|next| next.strip_suffix(')'))
+                .and_then(|next| next.split_once(", "))
+                .ok_or_else(|| {
+                    ParseError::new("(<left>, <right>)").at_token(line, next)
+                })?;
+
+            Ok((node.to_string(), vec![left.to_string(), right.to_string()]))
+        })
+        .map_err(|err| err.below(2))?;
+
+        Ok(Self { instructions, nodes: nodes.into_iter().collect() })
     }
 }
 
@@ -72,7 +68,7 @@ impl Network {
             steps += 1;
 
             let current = match instruction {
-                Instruction::Left => pos.get(0).expect("should work"),
+                Instruction::Left => pos.first().expect("should work"),
                 Instruction::Right => pos.get(1).expect("should work"),
             };
 
@@ -86,23 +82,17 @@ impl Network {
     }
 }
 
-fn part01(path: &str) -> usize {
-    let network = std::fs::read_to_string(path)
-        .expect("should be able to read file")
-        .parse::<Network>()
-        .expect("parsing");
+fn parse_input(input: &str) -> Result<Network, ParseError> {
+    input.parse::<Network>()
+}
 
+pub fn part01(network: &Network) -> usize {
     let goal = |v: &String| v == &"ZZZ".to_string();
 
     network.solve(&"AAA".to_string(), goal)
 }
 
-fn part02(path: &str) -> usize {
-    let network = std::fs::read_to_string(path)
-        .expect("should be able to read file")
-        .parse::<Network>()
-        .expect("parsing");
-
+pub fn part02(network: &Network) -> usize {
     let goal = |v: &String| v.ends_with('Z');
 
     network
@@ -126,27 +116,20 @@ const fn gcd(mut a: usize, mut b: usize) -> usize {
     a
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day08;
 
-    #[test]
-    fn part01_example() {
-        assert_eq!(part01("data/y2023/day08-example1.txt"), 2);
-    }
+impl Solution for Day08 {
+    type Input = Network;
 
-    #[test]
-    fn part01_input() {
-        assert_eq!(part01("data/y2023/day08.txt"), 13771);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn part02_example() {
-        assert_eq!(part02("data/y2023/day08-example2.txt"), 6);
+    fn part1(input: &Self::Input) -> Answer {
+        part01(input).into()
     }
 
-    #[test]
-    fn part02_input() {
-        assert_eq!(part02("data/y2023/day08.txt"), 13_129_439_557_681);
+    fn part2(input: &Self::Input) -> Answer {
+        part02(input).into()
     }
 }
@@ -1,3 +1,7 @@
+use crate::error::ParseError;
+use crate::io;
+use crate::solution::{Answer, Solution};
+
 fn predict_next(s: &[i32]) -> i32 {
     s.last().map_or(0, |&v| v + predict_next(&history_difference(s)))
 }
@@ -6,52 +10,39 @@ fn history_difference(s: &[i32]) -> Vec<i32> {
     s.windows(2).map(|w| w[1] - w[0]).collect()
 }
 
-fn parse_input(path: &str) -> Vec<Vec<i32>> {
-    std::fs::read_to_string(path)
-        .expect("should be able to read file")
-        .lines()
-        .map(|line| {
-            line.split_whitespace()
-                .filter_map(|v| v.parse::<i32>().ok())
-                .collect::<Vec<_>>()
-        })
-        .collect()
+fn parse_input(input: &str) -> Result<Vec<Vec<i32>>, ParseError> {
+    io::parse_lines(input, |line| {
+        line.split_whitespace().map(|v| io::parse_token(line, v)).collect()
+    })
 }
 
-fn part01(path: &str) -> i32 {
-    parse_input(path).iter().map(|history| predict_next(history)).sum()
+pub fn part01(histories: &[Vec<i32>]) -> i32 {
+    histories.iter().map(|history| predict_next(history)).sum()
 }
 
-fn part02(path: &str) -> i32 {
-    parse_input(path)
-        .into_iter()
+pub fn part02(histories: &[Vec<i32>]) -> i32 {
+    histories
+        .iter()
         .map(|history| {
-            predict_next(&history.into_iter().rev().collect::<Vec<_>>())
+            predict_next(&history.iter().rev().copied().collect::<Vec<_>>())
         })
         .sum()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day09;
 
-    #[test]
-    fn part01_example() {
-        assert_eq!(part01("data/y2023/day09-example1.txt"), 114);
-    }
+impl Solution for Day09 {
+    type Input = Vec<Vec<i32>>;
 
-    #[test]
-    fn part01_input() {
-        assert_eq!(part01("data/y2023/day09.txt"), 1_938_731_307);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn part02_example() {
-        assert_eq!(part02("data/y2023/day09-example1.txt"), 2);
+    fn part1(input: &Self::Input) -> Answer {
+        part01(input).into()
     }
 
-    #[test]
-    fn part02_input() {
-        assert_eq!(part02("data/y2023/day09.txt"), 948);
+    fn part2(input: &Self::Input) -> Answer {
+        part02(input).into()
     }
 }
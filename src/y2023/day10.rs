@@ -1,12 +1,17 @@
-use crate::grid::Position2D;
+use crate::error::ParseError;
+use crate::geometry;
+use crate::grid::{Direction, Position2D};
+use crate::io;
+use crate::point::Point2;
 use crate::search::bfs;
+use crate::solution::{Answer, Solution};
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::hash::Hash;
 
 #[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
-enum Pipe {
-    Start,
+pub enum Pipe {
+    Ground,
     Vertical,
     Horizontal,
     UpperLeft,
@@ -16,173 +21,150 @@ enum Pipe {
 }
 
 impl TryFrom<char> for Pipe {
-    type Error = &'static str;
+    type Error = ParseError;
 
     fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
+            '.' => Ok(Self::Ground),
             '|' => Ok(Self::Vertical),
             '-' => Ok(Self::Horizontal),
             'L' => Ok(Self::LowerLeft),
             'J' => Ok(Self::LowerRight),
             '7' => Ok(Self::UpperRight),
             'F' => Ok(Self::UpperLeft),
-            'S' => Ok(Self::Start),
-            _ => Err("unexpected pipe"),
+            _ => Err(ParseError::new(
+                "a pipe (|, -, L, J, 7, F, S) or ground (.)",
+            )),
         }
     }
 }
 
+impl Pipe {
+    // the sides the pipe connects, in the order of Direction::CARDINAL
+    const fn openings(self) -> &'static [Direction] {
+        match self {
+            Self::Ground => &[],
+            Self::Vertical => &[Direction::Up, Direction::Down],
+            Self::Horizontal => &[Direction::Right, Direction::Left],
+            Self::UpperLeft => &[Direction::Right, Direction::Down],
+            Self::UpperRight => &[Direction::Down, Direction::Left],
+            Self::LowerLeft => &[Direction::Up, Direction::Right],
+            Self::LowerRight => &[Direction::Up, Direction::Left],
+        }
+    }
+}
+
+pub struct Maze {
+    start: Position2D,
+    pipes: HashMap<Position2D, Pipe>,
+}
+
 fn neighbours(
     tile: (Position2D, Pipe),
     grid: &HashMap<Position2D, Pipe>,
 ) -> Vec<(Position2D, Pipe)> {
-    let neighbours = match tile.1 {
-        Pipe::Start => vec![
-            Position2D::new(1, 0),
-            Position2D::new(-1, 0),
-            Position2D::new(0, -1),
-            Position2D::new(0, 1),
-        ],
-        Pipe::Vertical => {
-            vec![Position2D::new(0, 1), Position2D::new(0, -1)]
-        }
-        Pipe::Horizontal => {
-            vec![Position2D::new(1, 0), Position2D::new(-1, 0)]
-        }
-        Pipe::UpperLeft => {
-            vec![Position2D::new(1, 0), Position2D::new(0, 1)]
-        }
-        Pipe::UpperRight => {
-            vec![Position2D::new(-1, 0), Position2D::new(0, 1)]
-        }
-        Pipe::LowerLeft => {
-            vec![Position2D::new(1, 0), Position2D::new(0, -1)]
-        }
-        Pipe::LowerRight => {
-            vec![Position2D::new(-1, 0), Position2D::new(0, -1)]
-        }
-    };
-
-    neighbours
+    tile.1
+        .openings()
         .iter()
-        .filter_map(|pos| {
-            let current = *pos + tile.0;
+        .filter_map(|&direction| {
+            let current = tile.0 + direction;
 
             grid.get(&current).map(|pipe| (current, *pipe))
         })
         .collect::<Vec<_>>()
 }
 
-fn parse_input(path: &str) -> HashMap<Position2D, Pipe> {
-    std::fs::read_to_string(path)
-        .expect("expected file")
-        .lines()
-        .enumerate()
-        .flat_map(|(y, line)| {
-            line.chars().enumerate().filter_map(move |(x, char)| {
-                let pos = Position2D {
-                    x: x.try_into().ok()?,
-                    y: y.try_into().ok()?,
-                };
-
-                Pipe::try_from(char).ok().map(|v| (pos, v))
+// the pipe under the start joins the pipes around it that lead into it,
+// leaving the start as it is when no pipe would
+fn hidden_pipe(around: io::Around) -> char {
+    let joined = Direction::CARDINAL
+        .into_iter()
+        .zip(around)
+        .filter(|&(direction, char)| {
+            char.and_then(|c| Pipe::try_from(c).ok()).is_some_and(|pipe| {
+                pipe.openings().contains(&direction.turn_around())
             })
         })
-        .collect()
-}
+        .map(|(direction, _)| direction)
+        .collect::<Vec<_>>();
 
-fn start_position(grid: &HashMap<Position2D, Pipe>) -> (Position2D, Pipe) {
-    grid.iter()
-        .find(|(_, p)| **p == Pipe::Start)
-        .map(|(p, v)| (*p, *v))
-        .expect("grid needs a start position")
+    "|-LJ7F"
+        .chars()
+        .find(|&c| Pipe::try_from(c).is_ok_and(|p| p.openings() == joined))
+        .unwrap_or('S')
 }
 
-fn part01(path: &str) -> usize {
-    let grid = parse_input(path);
-
-    let start_position = start_position(&grid);
-
-    let neighbours =
-        |position: (Position2D, Pipe)| neighbours(position, &grid);
-
-    let result = bfs(start_position, neighbours);
+fn parse_input(input: &str) -> Result<Maze, ParseError> {
+    let (grid, markers) =
+        io::parse_marked_grid(input, &['S'], |_, around| hidden_pipe(around))?;
+    let pipes = grid
+        .iter()
+        .filter(|(_, pipe)| **pipe != Pipe::Ground)
+        .map(|(position, pipe)| (position, *pipe))
+        .collect();
 
-    result.len() / 2
+    Ok(Maze { start: markers[&'S'], pipes })
 }
 
-fn part02(path: &str) -> usize {
-    let grid = parse_input(path);
-
-    let start_position = start_position(&grid);
+pub fn part01(maze: &Maze) -> usize {
+    let start = (maze.start, maze.pipes[&maze.start]);
 
     let neighbours =
-        |position: (Position2D, Pipe)| neighbours(position, &grid);
+        |position: (Position2D, Pipe)| neighbours(position, &maze.pipes);
 
-    let shape = bfs(start_position, neighbours)
-        .into_iter()
-        .collect::<HashMap<Position2D, Pipe>>();
+    let result = bfs(start, neighbours);
 
-    let x_max = shape.keys().map(|position| position.x).max().unwrap_or(0);
-    let y_max = shape.keys().map(|position| position.y).max().unwrap_or(0);
+    result.len() / 2
+}
 
-    let mut count = 0;
+// the tiles of the loop in order, starting from the start tile
+fn trace_loop(maze: &Maze) -> Vec<Position2D> {
+    let grid = &maze.pipes;
+    let connected = |position: Position2D| {
+        neighbours((position, grid[&position]), grid)
+            .into_iter()
+            .map(|(position, _)| position)
+    };
 
-    for y in 0..=y_max {
-        let mut inside = false;
+    let mut current =
+        connected(maze.start).next().expect("start should be on a loop");
+    let mut tiles = vec![maze.start];
 
-        for x in 0..=x_max {
-            let current_position = Position2D::new(x, y);
+    while current != maze.start {
+        let previous = tiles[tiles.len() - 1];
+        tiles.push(current);
 
-            match shape.get(&current_position) {
-                Some(Pipe::Vertical | Pipe::LowerRight | Pipe::LowerLeft) => {
-                    inside = !inside;
-                }
-                None if inside => count += 1,
-                _ => {}
-            };
-        }
+        current = connected(current)
+            .find(|&position| position != previous)
+            .expect("pipes should form a loop");
     }
 
-    count
+    tiles
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn part01_example1() {
-        assert_eq!(part01("data/y2023/day10-example1.txt"), 4);
-    }
+pub fn part02(maze: &Maze) -> i64 {
+    let vertices = trace_loop(maze)
+        .into_iter()
+        .map(|p| Point2::new(i64::from(p.x), i64::from(p.y)))
+        .collect::<Vec<_>>();
 
-    #[test]
-    fn part01_example2() {
-        assert_eq!(part01("data/y2023/day10-example2.txt"), 8);
-    }
+    geometry::interior_points(&vertices)
+}
 
-    #[test]
-    fn part01_input() {
-        assert_eq!(part01("data/y2023/day10.txt"), 6815);
-    }
+pub struct Day10;
 
-    #[test]
-    fn part02_example3() {
-        assert_eq!(part02("data/y2023/day10-example3.txt"), 4);
-    }
+impl Solution for Day10 {
+    type Input = Maze;
 
-    #[test]
-    fn part02_example4() {
-        assert_eq!(part02("data/y2023/day10-example4.txt"), 8);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn part02_example5() {
-        assert_eq!(part02("data/y2023/day10-example5.txt"), 10);
+    fn part1(input: &Self::Input) -> Answer {
+        part01(input).into()
     }
 
-    #[test]
-    fn part02_input() {
-        assert_eq!(part02("data/y2023/day10.txt"), 269);
+    fn part2(input: &Self::Input) -> Answer {
+        part02(input).into()
     }
 }
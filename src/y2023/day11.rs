@@ -1,7 +1,10 @@
+use crate::error::ParseError;
+use crate::io;
 use std::collections::HashMap;
 
 use crate::grid;
 use crate::grid::Position2D;
+use crate::solution::{Answer, Solution};
 
 #[derive(Debug, Eq, PartialEq)]
 enum Universe {
@@ -10,19 +13,19 @@ enum Universe {
 }
 
 impl TryFrom<char> for Universe {
-    type Error = &'static str;
+    type Error = ParseError;
 
     fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
             '.' => Ok(Self::EmptySpace),
             '#' => Ok(Self::Galaxy),
-            _ => Err("unexpected entity"),
+            _ => Err(ParseError::new("empty space (.) or a galaxy (#)")),
         }
     }
 }
 
 #[derive(Debug, Eq, PartialEq)]
-struct Image {
+pub struct Image {
     map: HashMap<Position2D, Universe>,
     x_max: i32,
     y_max: i32,
@@ -70,26 +73,12 @@ impl Image {
     }
 }
 
-fn parse_input(path: &str) -> Image {
-    let map = std::fs::read_to_string(path)
-        .expect("expected file")
-        .lines()
-        .enumerate()
-        .flat_map(|(y, line)| {
-            line.chars().enumerate().filter_map(move |(x, char)| {
-                let pos = Position2D {
-                    x: x.try_into().ok()?,
-                    y: y.try_into().ok()?,
-                };
-
-                Universe::try_from(char).ok().map(|v| (pos, v))
-            })
-        })
-        .collect();
+fn parse_input(input: &str) -> Result<Image, ParseError> {
+    let map = io::parse_grid(input)?;
 
     let (x_max, y_max) = grid::dimensions(&map);
 
-    Image { map, x_max, y_max }
+    Ok(Image { map, x_max, y_max })
 }
 
 fn generate_pairs<T: Copy>(input: &[T]) -> Vec<(T, T)> {
@@ -124,39 +113,28 @@ fn solve(image: &Image, scale_factor: i32) -> usize {
         .sum()
 }
 
-fn part01(path: &str) -> usize {
-    let image = parse_input(path);
-
-    solve(&image, 2)
+pub fn part01(image: &Image) -> usize {
+    solve(image, 2)
 }
 
-fn part02(path: &str) -> usize {
-    let image = parse_input(path);
-
-    solve(&image, 1_000_000)
+pub fn part02(image: &Image) -> usize {
+    solve(image, 1_000_000)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day11;
 
-    #[test]
-    fn part01_example1() {
-        assert_eq!(part01("data/y2023/day11-example1.txt"), 374);
-    }
+impl Solution for Day11 {
+    type Input = Image;
 
-    #[test]
-    fn part01_input() {
-        assert_eq!(part01("data/y2023/day11.txt"), 9_795_148);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn part02_example1() {
-        assert_eq!(part02("data/y2023/day11-example1.txt"), 82_000_210);
+    fn part1(input: &Self::Input) -> Answer {
+        part01(input).into()
     }
 
-    #[test]
-    fn part02_input() {
-        assert_eq!(part02("data/y2023/day11.txt"), 650_672_493_820);
+    fn part2(input: &Self::Input) -> Answer {
+        part02(input).into()
     }
 }
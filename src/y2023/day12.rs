@@ -1,3 +1,6 @@
+use crate::error::ParseError;
+use crate::io;
+use crate::solution::{Answer, Solution};
 use std::collections::HashMap;
 
 #[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
@@ -8,20 +11,20 @@ enum Spring {
 }
 
 impl TryFrom<char> for Spring {
-    type Error = &'static str;
+    type Error = ParseError;
 
     fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
             '.' => Ok(Self::Operational),
             '#' => Ok(Self::Damaged),
             '?' => Ok(Self::Unknown),
-            _ => Err("unexpected spring"),
+            _ => Err(ParseError::new("a spring (., # or ?)")),
         }
     }
 }
 
 #[derive(Debug)]
-struct Row {
+pub struct Row {
     springs: Vec<Spring>,
     damaged: Vec<usize>,
 }
@@ -59,30 +62,25 @@ fn consecutive_elements<T: Eq>(input: &[T]) -> Vec<(&T, usize)> {
     })
 }
 
-fn parse_input(path: &str) -> Vec<Row> {
-    std::fs::read_to_string(path)
-        .expect("expected file")
-        .lines()
-        .map(|line| {
-            line.split_once(' ')
-                .map(|(left, right)| {
-                    let springs = left
-                        .chars()
-                        .filter_map(|ch| Spring::try_from(ch).ok())
-                        .collect();
-
-                    let damaged = right
-                        .split(',')
-                        .filter_map(|s| s.parse().ok())
-                        .collect();
-
-                    Row { springs, damaged }
-                })
-                .expect("correct input")
-        })
-        .collect()
+fn parse_input(input: &str) -> Result<Vec<Row>, ParseError> {
+    io::parse_lines(input, |line| {
+        let (left, right) = line
+            .split_once(' ')
+            .ok_or_else(|| ParseError::new("springs and damaged groups"))?;
+
+        let springs = io::parse_row(left)?;
+
+        let damaged = right
+            .split(',')
+            .map(|s| io::parse_token(line, s))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Row { springs, damaged })
+    })
 }
 
+type Memo<'a> = HashMap<(&'a [Spring], &'a [usize], Option<usize>), usize>;
+
 fn resolve(
     springs: &[Spring],
     damaged: &[usize],
@@ -92,7 +90,7 @@ fn resolve(
         springs: &'a [Spring],
         damaged: &'a [usize],
         damaged_group: Option<usize>,
-        memo: &mut HashMap<(&'a [Spring], &'a [usize], Option<usize>), usize>,
+        memo: &mut Memo<'a>,
     ) -> usize {
         let key = (springs, damaged, damaged_group);
 
@@ -140,9 +138,7 @@ fn resolve(
     resolve_memo(springs, damaged, damaged_group, &mut HashMap::new())
 }
 
-fn part01(path: &str) -> usize {
-    let rows = parse_input(path);
-
+pub fn part01(rows: &[Row]) -> usize {
     rows.iter().map(|row| resolve(&row.springs, &row.damaged, None)).sum()
 }
 
@@ -161,9 +157,8 @@ fn unfold<T: Copy>(input: &[T], n: usize, separator: Option<T>) -> Vec<T> {
         .collect()
 }
 
-fn part02(path: &str) -> usize {
-    parse_input(path)
-        .iter()
+pub fn part02(rows: &[Row]) -> usize {
+    rows.iter()
         .map(|row| Row {
             springs: unfold(&row.springs, 5, Some(Spring::Unknown)),
             damaged: unfold(&row.damaged, 5, None),
@@ -172,27 +167,20 @@ fn part02(path: &str) -> usize {
         .sum()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day12;
 
-    #[test]
-    fn part01_example1() {
-        assert_eq!(part01("data/y2023/day12-example1.txt"), 21);
-    }
+impl Solution for Day12 {
+    type Input = Vec<Row>;
 
-    #[test]
-    fn part01_input() {
-        assert_eq!(part01("data/y2023/day12.txt"), 7694);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn part01_example2() {
-        assert_eq!(part02("data/y2023/day12-example1.txt"), 525_152);
+    fn part1(input: &Self::Input) -> Answer {
+        part01(input).into()
     }
 
-    #[test]
-    fn part02_input() {
-        assert_eq!(part02("data/y2023/day12.txt"), 5_071_883_216_318);
+    fn part2(input: &Self::Input) -> Answer {
+        part02(input).into()
     }
 }
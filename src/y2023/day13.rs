@@ -1,63 +1,52 @@
+use crate::error::ParseError;
+use crate::grid::{Grid, Transform, View};
+use crate::io;
+use crate::solution::{Answer, Solution};
+
 #[derive(Debug, PartialEq, Copy, Clone)]
-enum Pattern {
+pub enum Pattern {
     Ash,
     Rocks,
 }
 
 impl TryFrom<char> for Pattern {
-    type Error = &'static str;
+    type Error = ParseError;
 
     fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
             '.' => Ok(Self::Ash),
             '#' => Ok(Self::Rocks),
-            _ => Err("unexpected pattern"),
+            _ => Err(ParseError::new("ash (.) or rocks (#)")),
         }
     }
 }
 
-fn parse_input(path: &str) -> Vec<Vec<Vec<Pattern>>> {
-    std::fs::read_to_string(path)
-        .expect("expected file")
-        .split("\n\n")
-        .map(|chunk| {
-            chunk
-                .lines()
-                .map(|line| {
-                    line.chars()
-                        .filter_map(|ch| Pattern::try_from(ch).ok())
-                        .collect()
-                })
-                .collect()
-        })
-        .collect()
-}
-
-fn count_differences(left: &[Pattern], right: &[Pattern]) -> usize {
-    left.iter().zip(right.iter()).filter(|&(l, r)| l != r).count()
+fn parse_input(input: &str) -> Result<Vec<Grid<Pattern>>, ParseError> {
+    io::parse_chunks(input, |chunk| {
+        Grid::from_rows(io::parse_lines(chunk, io::parse_row)?)
+    })
 }
 
-fn transpose_column(pattern: &[Vec<Pattern>], column: usize) -> Vec<Pattern> {
-    pattern
-        .iter()
-        .map(|line| *line.get(column).expect("value should be there"))
-        .collect()
+fn count_differences<'a>(
+    left: impl Iterator<Item = &'a Pattern>,
+    right: impl Iterator<Item = &'a Pattern>,
+) -> usize {
+    left.zip(right).filter(|&(l, r)| l != r).count()
 }
 
 fn range(start: usize, max: usize) -> Vec<(usize, usize)> {
     (0..=start).rev().zip(start + 1..max).collect()
 }
 
-fn find_vertical_mirror(pattern: &[Vec<Pattern>], goal_total: usize) -> usize {
-    (0..pattern[0].len() - 1)
+// the number of rows above a horizontal mirror, vertical ones being found
+// on the transposed pattern
+fn find_reflection(pattern: View<Pattern>, goal_total: usize) -> usize {
+    (0..pattern.height() - 1)
         .find_map(|start| {
-            if range(start, pattern[0].len())
+            if range(start, pattern.height())
                 .iter()
-                .map(|(left, right)| {
-                    count_differences(
-                        &transpose_column(pattern, *left),
-                        &transpose_column(pattern, *right),
-                    )
+                .map(|(up, down)| {
+                    count_differences(pattern.row(*up), pattern.row(*down))
                 })
                 .sum::<usize>()
                 == goal_total
@@ -70,59 +59,33 @@ fn find_vertical_mirror(pattern: &[Vec<Pattern>], goal_total: usize) -> usize {
         .unwrap_or(0)
 }
 
-fn find_horizontal_mirror(pattern: &Vec<Vec<Pattern>>, goal: usize) -> usize {
-    (0..pattern.len() - 1)
-        .find_map(|start| {
-            if range(start, pattern.len())
-                .iter()
-                .map(|(up, down)| {
-                    count_differences(&pattern[*up], &pattern[*down])
-                })
-                .sum::<usize>()
-                == goal
-            {
-                Some((start + 1) * 100)
-            } else {
-                None
-            }
-        })
-        .unwrap_or(0)
-}
-
-fn find_mirror(pattern: &Vec<Vec<Pattern>>, goal_total: usize) -> usize {
-    find_horizontal_mirror(pattern, goal_total)
-        + find_vertical_mirror(pattern, goal_total)
+fn find_mirror(pattern: &Grid<Pattern>, goal_total: usize) -> usize {
+    find_reflection(pattern.view(Transform::Identity), goal_total) * 100
+        + find_reflection(pattern.view(Transform::Transpose), goal_total)
 }
 
-fn part01(path: &str) -> usize {
-    parse_input(path).iter().map(|pattern| find_mirror(pattern, 0)).sum()
+pub fn part01(patterns: &[Grid<Pattern>]) -> usize {
+    patterns.iter().map(|pattern| find_mirror(pattern, 0)).sum()
 }
 
-fn part02(path: &str) -> usize {
-    parse_input(path).iter().map(|pattern| find_mirror(pattern, 1)).sum()
+pub fn part02(patterns: &[Grid<Pattern>]) -> usize {
+    patterns.iter().map(|pattern| find_mirror(pattern, 1)).sum()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day13;
 
-    #[test]
-    fn part01_example1() {
-        assert_eq!(part01("data/y2023/day13-example1.txt"), 405);
-    }
+impl Solution for Day13 {
+    type Input = Vec<Grid<Pattern>>;
 
-    #[test]
-    fn part01_input() {
-        assert_eq!(part01("data/y2023/day13.txt"), 33047);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn part02_example1() {
-        assert_eq!(part02("data/y2023/day13-example1.txt"), 400);
+    fn part1(input: &Self::Input) -> Answer {
+        part01(input).into()
     }
 
-    #[test]
-    fn part02_input() {
-        assert_eq!(part02("data/y2023/day13.txt"), 28806);
+    fn part2(input: &Self::Input) -> Answer {
+        part02(input).into()
     }
 }
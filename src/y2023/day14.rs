@@ -1,43 +1,41 @@
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::io;
+use crate::solution::{Answer, Solution};
 use itertools::Itertools;
 use std::collections::HashMap;
 
 #[derive(Debug, Eq, PartialEq, Copy, Clone, Ord, PartialOrd, Hash)]
-enum Entities {
+pub enum Entities {
     RoundRock,
     SquareRock,
     EmptySpace,
 }
 
 impl TryFrom<char> for Entities {
-    type Error = &'static str;
+    type Error = ParseError;
 
     fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
             'O' => Ok(Self::RoundRock),
             '#' => Ok(Self::SquareRock),
             '.' => Ok(Self::EmptySpace),
-            _ => Err("unexpected rock"),
+            _ => Err(ParseError::new("a rock (O or #) or empty space (.)")),
         }
     }
 }
 
-fn parse_input(path: &str) -> Vec<Vec<Entities>> {
-    std::fs::read_to_string(path)
-        .expect("expected file")
-        .lines()
-        .map(|line| {
-            line.chars().filter_map(|ch| Entities::try_from(ch).ok()).collect()
-        })
-        .collect()
+fn parse_input(input: &str) -> Result<Grid<Entities>, ParseError> {
+    Grid::from_rows(io::parse_lines(input, io::parse_row)?)
 }
 
-fn total_load(grid: &[Vec<Entities>]) -> usize {
-    grid.iter()
+fn total_load(grid: &Grid<Entities>) -> usize {
+    grid.rows()
         .enumerate()
         .flat_map(|(y, row)| {
-            row.iter().enumerate().filter_map(move |(_, entity)| {
+            row.iter().filter_map(move |entity| {
                 if *entity == Entities::RoundRock {
-                    Some(grid.len() - y)
+                    Some(grid.height() - y)
                 } else {
                     None
                 }
@@ -46,8 +44,10 @@ fn total_load(grid: &[Vec<Entities>]) -> usize {
         .sum()
 }
 
-fn tilt_row_right(r: &[Entities]) -> Vec<Entities> {
-    r.iter()
+fn tilt_column<'a>(
+    column: impl Iterator<Item = &'a Entities>,
+) -> Vec<Entities> {
+    column
         .copied()
         .chunk_by(|&c| c == Entities::SquareRock)
         .into_iter()
@@ -55,65 +55,30 @@ fn tilt_row_right(r: &[Entities]) -> Vec<Entities> {
         .collect()
 }
 
-fn transpose<T: Copy>(matrix: &[Vec<T>]) -> Vec<Vec<T>> {
-    (0..matrix[0].len())
-        .map(|i| (0..matrix.len()).map(|j| matrix[j][i]).collect())
-        .collect()
-}
-
-fn tilt(matrix: &[Vec<Entities>]) -> Vec<Vec<Entities>> {
-    let v = transpose(matrix)
-        .iter()
-        .map(|row| tilt_row_right(row))
-        .collect::<Vec<_>>();
+fn tilt(grid: &Grid<Entities>) -> Grid<Entities> {
+    let columns = grid.columns().map(tilt_column).collect();
 
-    transpose(&v)
+    Grid::from_rows(columns)
+        .expect("tilting should keep column heights")
+        .transpose()
 }
 
-fn part01(path: &str) -> usize {
-    let grid = parse_input(path);
-    total_load(&tilt(&grid))
+pub fn part01(grid: &Grid<Entities>) -> usize {
+    total_load(&tilt(grid))
 }
 
-fn rot_ccw(grid: &[Vec<Entities>]) -> Vec<Vec<Entities>> {
-    transpose(grid)
-        .iter()
-        .map(|row| row.iter().rev().copied().collect())
-        .collect()
-}
-
-fn spin_cycle(grid: Vec<Vec<Entities>>) -> Vec<Vec<Entities>> {
-    (0..4).fold(grid, |acc, _| tilt(&rot_ccw(&acc)))
+// tilts north, west, south and east, rotating the next side to the north
+// after each tilt
+fn spin_cycle(grid: Grid<Entities>) -> Grid<Entities> {
+    (0..4).fold(grid, |acc, _| tilt(&acc).rotate_cw())
 }
 
-fn part02(path: &str) -> usize {
-    let grid = parse_input(path);
-
-    let mut foo = grid.clone();
-
-    foo = spin_cycle(foo);
-
-    for y in 0..foo.len() {
-        for x in 0..foo[0].len() {
-            print!(
-                "{}",
-                match foo[y][x] {
-                    Entities::RoundRock => 'O',
-                    Entities::SquareRock => '#',
-                    Entities::EmptySpace => '.',
-                }
-            );
-        }
-        println!();
-    }
-
-    return 0;
-
-    //let goal = 1_000_000_000;
-    let goal = 2;
+pub fn part02(grid: &Grid<Entities>) -> usize {
+    let goal = 1_000_000_000;
     let mut seen = HashMap::new();
     let mut cycle_number = 1;
-    let mut current = grid;
+    let mut current = grid.clone();
+
     while cycle_number <= goal {
         let updated = spin_cycle(current);
 
@@ -121,54 +86,60 @@ fn part02(path: &str) -> usize {
             let cycle_length = cycle_number - previous_cycle;
             let remaining = (goal - cycle_number) % cycle_length;
 
-            current =
-                (0..remaining).fold(updated.clone(), |acc, _| spin_cycle(acc));
+            current = (0..remaining).fold(updated, |acc, _| spin_cycle(acc));
 
             break;
         }
+
         seen.insert(updated.clone(), cycle_number);
         cycle_number += 1;
-        current = updated.clone();
-
-        println!("----------");
-
-        for y in 0..updated.len() {
-            for x in 0..updated[0].len() {
-                print!(
-                    "{}",
-                    match updated[y][x] {
-                        Entities::RoundRock => 'O',
-                        Entities::SquareRock => '#',
-                        Entities::EmptySpace => '.',
-                    }
-                );
-            }
-            println!();
-        }
-
-        println!("<---------->");
+        current = updated;
     }
 
     total_load(&current)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day14;
 
-    #[test]
-    fn part01_example1() {
-        assert_eq!(part01("data/y2023/day14-example1.txt"), 136);
+impl Solution for Day14 {
+    type Input = Grid<Entities>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn part01_input() {
-        assert_eq!(part01("data/y2023/day14.txt"), 105_249);
+    fn part1(input: &Self::Input) -> Answer {
+        part01(input).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        part02(input).into()
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::Render;
 
     #[test]
-    #[ignore = "needs fixing"]
-    fn part02_example1() {
-        assert_eq!(part02("data/y2023/day14-example1.txt"), 64);
+    fn one_spin_cycle() {
+        let grid = parse_input(
+            "O....#....\nO.OO#....#\n.....##...\nOO.#O....O\n.O.....O#.\n\
+             O.#..O.#.#\n..O..#O..O\n.......O..\n#....###..\n#OO..#....\n",
+        )
+        .unwrap();
+
+        let render = Render::grid(&spin_cycle(grid), |entity| match entity {
+            Entities::RoundRock => 'O',
+            Entities::SquareRock => '#',
+            Entities::EmptySpace => '.',
+        });
+
+        assert_eq!(
+            render.to_string(),
+            ".....#....\n....#...O#\n...OO##...\n.OO#......\n.....OOO#.\n\
+             .O#...O#.#\n....O#....\n......OOOO\n#...O###..\n#..OO#....\n"
+        );
     }
 }
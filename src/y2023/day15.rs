@@ -1,3 +1,6 @@
+use crate::error::ParseError;
+use crate::io;
+use crate::solution::{Answer, Solution};
 use std::collections::VecDeque;
 use std::str::FromStr;
 
@@ -5,12 +8,23 @@ fn hash(s: &str) -> usize {
     s.chars().fold(0, |acc, ch| ((acc + ch as usize) * 17) % 256)
 }
 
-fn part01(path: &str) -> usize {
-    std::fs::read_to_string(path)
-        .expect("expected file")
+fn parse_input(input: &str) -> Result<Vec<String>, ParseError> {
+    let line = input
         .lines()
-        .map(|line| line.split(',').map(hash).sum::<usize>())
-        .sum()
+        .next()
+        .ok_or_else(|| ParseError::new("an initialization sequence"))?;
+
+    line.split(',')
+        .map(|step| {
+            step.parse::<Instruction>()
+                .map(|_| step.to_string())
+                .map_err(|err| err.at_token(line, step))
+        })
+        .collect()
+}
+
+pub fn part01(steps: &[String]) -> usize {
+    steps.iter().map(|step| hash(step)).sum()
 }
 
 enum Instruction {
@@ -19,36 +33,30 @@ enum Instruction {
 }
 
 impl FromStr for Instruction {
-    type Err = &'static str;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.ends_with('-') {
-            let label = s.trim_end_matches('-');
-
+        if let Some(label) = s.strip_suffix('-') {
             Ok(Self::Remove(hash(label), label.to_string()))
         } else {
-            let (label, focal) = s.split_once('=').expect("should work");
+            let (label, focal) = s.split_once('=').ok_or_else(|| {
+                ParseError::new("<label>- or <label>=<focal length>")
+            })?;
 
             Ok(Self::Upsert(
                 hash(label),
                 label.to_string(),
-                focal.parse().expect("should be a number"),
+                io::parse_token(s, focal)?,
             ))
         }
     }
 }
 
-fn part02(path: &str) -> usize {
-    let instructions = std::fs::read_to_string(path)
-        .expect("expected file")
-        .lines()
-        .map(|line| {
-            line.split(',')
-                .filter_map(|s| s.parse::<Instruction>().ok())
-                .collect::<Vec<_>>()
-        })
-        .next()
-        .expect("expected one line");
+pub fn part02(steps: &[String]) -> usize {
+    let instructions = steps
+        .iter()
+        .filter_map(|s| s.parse::<Instruction>().ok())
+        .collect::<Vec<_>>();
 
     let mut boxes: [VecDeque<(String, usize)>; 256] =
         core::array::from_fn(|_| VecDeque::new());
@@ -84,27 +92,20 @@ fn part02(path: &str) -> usize {
     })
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day15;
 
-    #[test]
-    fn part01_example1() {
-        assert_eq!(part01("data/y2023/day15-example1.txt"), 1320);
-    }
+impl Solution for Day15 {
+    type Input = Vec<String>;
 
-    #[test]
-    fn part01_input() {
-        assert_eq!(part01("data/y2023/day15.txt"), 516_469);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn part02_example1() {
-        assert_eq!(part02("data/y2023/day15-example1.txt"), 145);
+    fn part1(input: &Self::Input) -> Answer {
+        part01(input).into()
     }
 
-    #[test]
-    fn part02_input() {
-        assert_eq!(part02("data/y2023/day15.txt"), 221_627);
+    fn part2(input: &Self::Input) -> Answer {
+        part02(input).into()
     }
 }
@@ -1,3 +1,5 @@
+use crate::registry::Puzzle;
+
 mod day01;
 mod day02;
 mod day03;
@@ -14,3 +16,23 @@ mod day13;
 mod day14;
 mod day15;
 mod day16;
+
+pub fn puzzles() -> Vec<Puzzle> {
+    vec![
+        Puzzle::new::<day01::Day01>(2023, 1),
+        Puzzle::new::<day02::Day02>(2023, 2),
+        Puzzle::new::<day03::Day03>(2023, 3),
+        Puzzle::new::<day04::Day04>(2023, 4),
+        Puzzle::new::<day05::Day05>(2023, 5),
+        Puzzle::new::<day06::Day06>(2023, 6),
+        Puzzle::new::<day07::Day07>(2023, 7),
+        Puzzle::new::<day08::Day08>(2023, 8),
+        Puzzle::new::<day09::Day09>(2023, 9),
+        Puzzle::new::<day10::Day10>(2023, 10),
+        Puzzle::new::<day11::Day11>(2023, 11),
+        Puzzle::new::<day12::Day12>(2023, 12),
+        Puzzle::new::<day13::Day13>(2023, 13),
+        Puzzle::new::<day14::Day14>(2023, 14),
+        Puzzle::new::<day15::Day15>(2023, 15),
+    ]
+}